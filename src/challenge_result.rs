@@ -1,22 +1,106 @@
 use std::error::Error;
+use std::fmt::{Display, Formatter};
+use num::{BigInt, Signed, Zero};
 
 pub type ChallengeResult = Result<Solution, Box<dyn Error>>;
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    BigInteger(BigInt),
+    Text(String),
+    /// Several lines meant to be read together, like letters drawn in ASCII art.
+    MultiLine(String),
+    Unsolved,
+}
+
+impl Answer {
+    /// Why a numeric answer is almost certainly not what the puzzle wants, if it looks that way.
+    pub fn suspicion(&self) -> Option<&'static str> {
+        match self {
+            Answer::Integer(0) => Some("zero"),
+            Answer::Integer(value) if *value < 0 => Some("negative"),
+            Answer::BigInteger(value) if value.is_zero() => Some("zero"),
+            Answer::BigInteger(value) if value.is_negative() => Some("negative"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::Text(text) | Answer::MultiLine(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value)
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::BigInteger(BigInt::from(value)))
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::BigInteger(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::MultiLine(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::from(text.to_string())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Solution {
-    pub part1: String,
-    pub part2: String,
+    pub part1: Answer,
+    pub part2: Answer,
 }
 
 impl Solution {
-    pub fn new(part1: String, part2: String) -> Solution {
+    pub fn new(part1: Answer, part2: Answer) -> Solution {
         Solution { part1, part2 }
     }
 
-    pub fn from<A: ToString, B: ToString>(part1: A, part2: B) -> Solution {
+    pub fn from<A: Into<Answer>, B: Into<Answer>>(part1: A, part2: B) -> Solution {
         Solution {
-            part1: part1.to_string(),
-            part2: part2.to_string(),
+            part1: part1.into(),
+            part2: part2.into(),
         }
     }
+
+    pub fn unsolved() -> Solution {
+        Solution::new(Answer::Unsolved, Answer::Unsolved)
+    }
 }
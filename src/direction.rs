@@ -0,0 +1,335 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use num::Signed;
+use crate::year2022::grid::FromChar;
+use crate::year2022::point::Point;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    pub const ALL: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::East,
+        Direction4::South,
+        Direction4::West,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction4> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn turn_left(self) -> Direction4 {
+        match self {
+            Direction4::North => Direction4::West,
+            Direction4::East => Direction4::North,
+            Direction4::South => Direction4::East,
+            Direction4::West => Direction4::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction4 {
+        match self {
+            Direction4::North => Direction4::East,
+            Direction4::East => Direction4::South,
+            Direction4::South => Direction4::West,
+            Direction4::West => Direction4::North,
+        }
+    }
+
+    pub fn turn_around(self) -> Direction4 {
+        match self {
+            Direction4::North => Direction4::South,
+            Direction4::East => Direction4::West,
+            Direction4::South => Direction4::North,
+            Direction4::West => Direction4::East,
+        }
+    }
+
+    /// Offset of one step where north increases y, as on a cartesian plane.
+    pub fn to_point_y_up<T: Signed>(self) -> Point<T> {
+        match self {
+            Direction4::North => Point::new(T::zero(), T::one()),
+            Direction4::East => Point::new(T::one(), T::zero()),
+            Direction4::South => Point::new(T::zero(), -T::one()),
+            Direction4::West => Point::new(-T::one(), T::zero()),
+        }
+    }
+
+    /// Offset of one step where north decreases y, as with grid rows read top to bottom.
+    pub fn to_point_y_down<T: Signed>(self) -> Point<T> {
+        let Point { x, y } = self.to_point_y_up::<T>();
+        Point::new(x, -y)
+    }
+}
+
+impl Display for Direction4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction4::North => "north",
+            Direction4::East => "east",
+            Direction4::South => "south",
+            Direction4::West => "west",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction8> {
+        Self::ALL.into_iter()
+    }
+
+    fn turn(self, eighths: usize) -> Direction8 {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns 45 degrees counter-clockwise.
+    pub fn turn_left(self) -> Direction8 {
+        self.turn(7)
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        self.turn(1)
+    }
+
+    pub fn turn_around(self) -> Direction8 {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Offset of one step where north increases y, as on a cartesian plane.
+    pub fn to_point_y_up<T: Signed>(self) -> Point<T> {
+        let (vertical, horizontal) = match self {
+            Direction8::North => (Some(Direction4::North), None),
+            Direction8::NorthEast => (Some(Direction4::North), Some(Direction4::East)),
+            Direction8::East => (None, Some(Direction4::East)),
+            Direction8::SouthEast => (Some(Direction4::South), Some(Direction4::East)),
+            Direction8::South => (Some(Direction4::South), None),
+            Direction8::SouthWest => (Some(Direction4::South), Some(Direction4::West)),
+            Direction8::West => (None, Some(Direction4::West)),
+            Direction8::NorthWest => (Some(Direction4::North), Some(Direction4::West)),
+        };
+
+        [vertical, horizontal]
+            .into_iter()
+            .flatten()
+            .fold(Point::new(T::zero(), T::zero()), |offset, direction| {
+                offset + direction.to_point_y_up()
+            })
+    }
+
+    /// Offset of one step where north decreases y, as with grid rows read top to bottom.
+    pub fn to_point_y_down<T: Signed>(self) -> Point<T> {
+        let Point { x, y } = self.to_point_y_up::<T>();
+        Point::new(x, -y)
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::North => Direction8::North,
+            Direction4::East => Direction8::East,
+            Direction4::South => Direction8::South,
+            Direction4::West => Direction8::West,
+        }
+    }
+}
+
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction8::North => "north",
+            Direction8::NorthEast => "northeast",
+            Direction8::East => "east",
+            Direction8::SouthEast => "southeast",
+            Direction8::South => "south",
+            Direction8::SouthWest => "southwest",
+            Direction8::West => "west",
+            Direction8::NorthWest => "northwest",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ParseDirectionError;
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "direction must be a letter, an arrow or a compass word")
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+impl FromStr for Direction4 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "n" | "u" | "^" | "north" | "up" => Ok(Direction4::North),
+            "e" | "r" | ">" | "east" | "right" => Ok(Direction4::East),
+            "s" | "d" | "v" | "south" | "down" => Ok(Direction4::South),
+            "w" | "l" | "<" | "west" | "left" => Ok(Direction4::West),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    /// Accepts everything `Direction4` does, plus the diagonals as letter pairs, arrows and
+    /// compass words with or without a hyphen.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(direction) = s.parse::<Direction4>() {
+            return Ok(direction.into());
+        }
+
+        match s.to_ascii_lowercase().replace('-', "").as_str() {
+            "ne" | "ur" | "↗" | "northeast" | "upright" => Ok(Direction8::NorthEast),
+            "se" | "dr" | "↘" | "southeast" | "downright" => Ok(Direction8::SouthEast),
+            "sw" | "dl" | "↙" | "southwest" | "downleft" => Ok(Direction8::SouthWest),
+            "nw" | "ul" | "↖" | "northwest" | "upleft" => Ok(Direction8::NorthWest),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl FromChar for Direction4 {
+    type Error = ParseDirectionError;
+
+    fn from_char(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+impl FromChar for Direction8 {
+    type Error = ParseDirectionError;
+
+    fn from_char(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direction4_turns() {
+        for direction in Direction4::iter() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.turn_around());
+            assert_eq!(direction.turn_left().turn_left().turn_left().turn_left(), direction);
+        }
+        assert_eq!(Direction4::North.turn_right(), Direction4::East);
+        assert_eq!(Direction4::North.turn_left(), Direction4::West);
+    }
+
+    #[test]
+    fn direction8_turns() {
+        for direction in Direction8::iter() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            let half_turn = (0..4).fold(direction, |turned, _| turned.turn_right());
+            assert_eq!(half_turn, direction.turn_around());
+            assert_eq!(direction.turn_around().turn_around(), direction);
+            assert_ne!(direction.is_diagonal(), direction.turn_right().is_diagonal());
+        }
+        assert_eq!(Direction8::North.turn_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.turn_around(), Direction8::NorthWest);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("U".parse::<Direction4>().unwrap(), Direction4::North);
+        assert_eq!("left".parse::<Direction4>().unwrap(), Direction4::West);
+        assert_eq!(Direction4::from_char('^').unwrap(), Direction4::North);
+        assert_eq!(Direction4::from_char('v').unwrap(), Direction4::South);
+        assert!(Direction4::from_char('x').is_err());
+        assert!("up-left".parse::<Direction4>().is_err());
+    }
+
+    #[test]
+    fn direction8_parsing() {
+        assert_eq!("U".parse::<Direction8>().unwrap(), Direction8::North);
+        assert_eq!("left".parse::<Direction8>().unwrap(), Direction8::West);
+        assert_eq!("NE".parse::<Direction8>().unwrap(), Direction8::NorthEast);
+        assert_eq!("south-west".parse::<Direction8>().unwrap(), Direction8::SouthWest);
+        assert_eq!("up-left".parse::<Direction8>().unwrap(), Direction8::NorthWest);
+        assert_eq!(Direction8::from_char('>').unwrap(), Direction8::East);
+        assert_eq!(Direction8::from_char('↘').unwrap(), Direction8::SouthEast);
+        assert!(Direction8::from_char('x').is_err());
+        assert!("north-north-east".parse::<Direction8>().is_err());
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(Direction4::North.to_point_y_up::<i32>(), Point::new(0, 1));
+        assert_eq!(Direction4::North.to_point_y_down::<i32>(), Point::new(0, -1));
+        assert_eq!(Direction4::West.to_point_y_down::<i32>(), Point::new(-1, 0));
+
+        for direction in Direction4::iter() {
+            let there = direction.to_point_y_up::<i64>();
+            let back = direction.turn_around().to_point_y_up::<i64>();
+            assert_eq!(there + back, Point::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn direction8_offsets() {
+        assert_eq!(Direction8::NorthEast.to_point_y_up::<i32>(), Point::new(1, 1));
+        assert_eq!(Direction8::NorthEast.to_point_y_down::<i32>(), Point::new(1, -1));
+        assert_eq!(Direction8::SouthWest.to_point_y_down::<i32>(), Point::new(-1, 1));
+
+        for direction in Direction4::iter() {
+            let offset = Direction8::from(direction).to_point_y_down::<i64>();
+            assert_eq!(offset, direction.to_point_y_down());
+        }
+
+        for direction in Direction8::iter() {
+            let there = direction.to_point_y_up::<i64>();
+            let back = direction.turn_around().to_point_y_up::<i64>();
+            assert_eq!(there + back, Point::new(0, 0));
+        }
+    }
+}
@@ -2,6 +2,9 @@ extern crate core;
 
 mod challenge_input;
 mod challenge_result;
+mod direction;
+mod parse_error;
+mod rational;
 mod year2021;
 mod year2022;
 
@@ -9,7 +12,7 @@ use std::env;
 use std::fmt;
 use std::error;
 use std::time::Instant;
-use crate::challenge_result::ChallengeResult;
+use crate::challenge_result::{Answer, ChallengeResult};
 
 struct Args {
     year: u16,
@@ -45,7 +48,9 @@ fn main() {
                 match run_challenge(&input, year, day) {
                     Ok(result) => {
                         let duration = start.elapsed();
-                        println!("{} : {}\nDuration: {:?}", result.part1, result.part2, duration);
+                        print_answer("Part 1", &result.part1);
+                        print_answer("Part 2", &result.part2);
+                        println!("Duration: {:?}", duration);
                     }
                     Err(e) => {
                         eprintln!("Challenge Error: {}", e);
@@ -62,6 +67,17 @@ fn main() {
     }
 }
 
+fn print_answer(label: &str, answer: &Answer) {
+    match answer {
+        Answer::MultiLine(text) => println!("{}:\n{}", label, text.trim_end()),
+        Answer::Unsolved => println!("{}: (unsolved)", label),
+        answer => match answer.suspicion() {
+            Some(reason) => println!("{}: {} (suspicious: {})", label, answer, reason),
+            None => println!("{}: {}", label, answer),
+        },
+    }
+}
+
 fn run_challenge(input: &str, year: u16, day: u8) -> ChallengeResult {
     match year {
         2021 => year2021::run_challenge(input, day),
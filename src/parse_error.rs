@@ -0,0 +1,254 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Malformed puzzle input, pointing at the line and the bytes within it that couldn't be read.
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    span: Range<usize>,
+    text: String,
+    cause: Box<dyn Error>,
+}
+
+impl ParseError {
+    /// `line` is the zero-based line index and `span` the byte range within that line. A span
+    /// that splits a character is widened to cover all of it.
+    pub fn new<E>(line: usize, text: &str, span: Range<usize>, cause: E) -> ParseError
+    where
+        E: Into<Box<dyn Error>>,
+    {
+        let mut start = span.start.min(text.len());
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = span.end.clamp(start, text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+
+        ParseError {
+            line,
+            span: start..end,
+            text: text.to_string(),
+            cause: cause.into(),
+        }
+    }
+
+    /// Locates a byte range of the whole input, which may come from matching across lines.
+    pub fn at_offset<E>(input: &str, span: Range<usize>, cause: E) -> ParseError
+    where
+        E: Into<Box<dyn Error>>,
+    {
+        let mut start = span.start.min(input.len());
+        while !input.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let line = input[..start].matches('\n').count();
+
+        let text = &input[line_start..line_end];
+        let span_end = span.end.clamp(start, line_end);
+        ParseError::new(line, text, start - line_start..span_end - line_start, cause)
+    }
+
+    /// One-based line number.
+    pub fn line(&self) -> usize {
+        self.line + 1
+    }
+
+    /// One-based column of the first offending character.
+    pub fn column(&self) -> usize {
+        self.text[..self.span.start].chars().count() + 1
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let number = self.line().to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = " ".repeat(self.column() - 1);
+        let underline = "^".repeat(self.text[self.span.clone()].chars().count().max(1));
+
+        writeln!(f, "{} on line {}, column {}", self.cause, self.line(), self.column())?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}{}", gutter, indent, underline)
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.cause.as_ref())
+    }
+}
+
+/// Attaches the input position to a failed parse.
+pub trait Context<T> {
+    /// Blames the whole of line `index`.
+    fn on_line(self, index: usize, line: &str) -> Result<T, ParseError>;
+
+    /// Blames `part`, which must be a slice of `line`, e.g. one field from `split`.
+    fn at(self, index: usize, line: &str, part: &str) -> Result<T, ParseError>;
+
+    /// Blames a byte range of the whole input.
+    fn at_offset(self, input: &str, span: Range<usize>) -> Result<T, ParseError>;
+}
+
+fn span_of(line: &str, part: &str) -> Range<usize> {
+    let start = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if start <= line.len() && start + part.len() <= line.len() {
+        start..start + part.len()
+    } else {
+        0..line.len()
+    }
+}
+
+impl<T, E> Context<T> for Result<T, E>
+where
+    E: Into<Box<dyn Error>>,
+{
+    fn on_line(self, index: usize, line: &str) -> Result<T, ParseError> {
+        self.map_err(|cause| ParseError::new(index, line, 0..line.len(), cause))
+    }
+
+    fn at(self, index: usize, line: &str, part: &str) -> Result<T, ParseError> {
+        self.map_err(|cause| ParseError::new(index, line, span_of(line, part), cause))
+    }
+
+    fn at_offset(self, input: &str, span: Range<usize>) -> Result<T, ParseError> {
+        self.map_err(|cause| ParseError::at_offset(input, span, cause))
+    }
+}
+
+/// Fails on anything but whitespace in the `skipped` bytes of `input`, such as the text between
+/// regex matches that should cover the whole input, blaming the rest of that line.
+pub fn check_skipped(input: &str, skipped: Range<usize>, expected: &str) -> Result<(), ParseError> {
+    match input[skipped.clone()].find(|c: char| !c.is_whitespace()) {
+        Some(offset) => {
+            let start = skipped.start + offset;
+            let end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+            Err(expected).at_offset(input, start..end)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Parses every line with `FromStr`, stopping at the first one that fails.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().on_line(i, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(line: &str, field: &str) -> ParseError {
+        let part = &line[line.find(field).unwrap()..][..field.len()];
+        Err::<(), _>("bad field").at(4, line, part).unwrap_err()
+    }
+
+    #[test]
+    fn points_at_the_field() {
+        let error = field("move 3 from x to 9", "x");
+        assert_eq!((error.line(), error.column()), (5, 13));
+        assert_eq!(
+            error.to_string(),
+            "bad field on line 5, column 13\n5 | move 3 from x to 9\n  |             ^"
+        );
+    }
+
+    #[test]
+    fn underlines_the_whole_span() {
+        let error = field("a: 12345, b: 7", "12345");
+        let underline = error.to_string().lines().last().unwrap().to_string();
+        assert_eq!(underline, "  |    ^^^^^");
+    }
+
+    #[test]
+    fn gutter_fits_the_line_number() {
+        let error = Err::<(), _>("bad line").on_line(99, "xyz").unwrap_err();
+        assert_eq!(error.to_string(), "bad line on line 100, column 1\n100 | xyz\n    | ^^^");
+    }
+
+    #[test]
+    fn offset_past_a_newline() {
+        let input = "first\nsecond ]\nthird";
+        let error = Err::<(), _>("unmatched").at_offset(input, 13..14).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 8));
+        assert!(error.to_string().ends_with("2 | second ]\n  |        ^"), "{}", error);
+    }
+
+    #[test]
+    fn offset_span_stops_at_the_end_of_its_line() {
+        let input = "ab\ncd";
+        let error = ParseError::at_offset(input, 1..5, "spans lines");
+        assert_eq!((error.line(), error.column()), (1, 2));
+        assert!(error.to_string().ends_with("1 | ab\n  |  ^"), "{}", error);
+    }
+
+    #[test]
+    fn empty_span_at_the_end_of_a_line() {
+        let input = "[1,2\n[3]";
+        let error = Err::<(), _>("expected ]").at_offset(input, 4..4).unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 5));
+        assert!(error.to_string().ends_with("1 | [1,2\n  |     ^"), "{}", error);
+    }
+
+    #[test]
+    fn columns_count_characters_not_bytes() {
+        let error = field("café → 12x", "12x");
+        assert_eq!(error.column(), 8);
+        assert!(error.to_string().ends_with("5 | café → 12x\n  |        ^^^"), "{}", error);
+    }
+
+    #[test]
+    fn spans_inside_a_character_cover_all_of_it() {
+        // 'é' is the two bytes 3..5 and '→' the three bytes 6..9
+        let error = ParseError::new(0, "café → 12x", 4..7, "split");
+        assert_eq!(error.column(), 4);
+        assert!(error.to_string().ends_with("1 | café → 12x\n  |    ^^^"), "{}", error);
+
+        let error = ParseError::new(0, "café", 4..2, "backwards");
+        assert!(error.to_string().ends_with("1 | café\n  |    ^"), "{}", error);
+
+        let error = ParseError::at_offset("ok\n→?", 4..5, "arrow");
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert!(error.to_string().ends_with("2 | →?\n  | ^"), "{}", error);
+    }
+
+    #[test]
+    fn part_outside_the_line_blames_all_of_it() {
+        let error = Err::<(), _>("bad").at(0, "abc", "elsewhere").unwrap_err();
+        assert_eq!(error.column(), 1);
+        assert!(error.to_string().ends_with("  | ^^^"), "{}", error);
+    }
+
+    #[test]
+    fn skipped_text_must_be_blank() {
+        let input = "item 1\n\n  junk here\nitem 2\n";
+        assert!(check_skipped(input, 6..8, "expected an item").is_ok());
+
+        let error = check_skipped(input, 6..input.len(), "expected an item").unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 3));
+        assert!(error.to_string().ends_with("3 |   junk here\n  |   ^^^^^^^^^"), "{}", error);
+    }
+
+    #[test]
+    fn parse_lines_reports_the_failing_line() {
+        let error = parse_lines::<u8>("1\n2\n300\n4").unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 1));
+        assert!(error.source().is_some());
+        assert_eq!(parse_lines::<u8>("1\n2\n").unwrap(), [1, 2]);
+    }
+}
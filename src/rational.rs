@@ -0,0 +1,418 @@
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+use num::bigint::ParseBigIntError;
+use num::rational::Ratio;
+use num::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+/// An exact fraction kept in lowest terms. Arithmetic runs on `i64` until a result would
+/// overflow, then carries on with big integers, dropping back to `i64` once values shrink again.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Rational(Repr);
+
+/// Every value that fits in `i64` is kept `Small`, so the derived equality and hashing can
+/// compare representations directly.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum Repr {
+    Small(Ratio<i64>),
+    Big(BigRational),
+}
+
+impl Rational {
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: i64, denominator: i64) -> Rational {
+        // normalising the sign would negate `i64::MIN`, which only fits once it's big
+        if numerator == i64::MIN || denominator == i64::MIN {
+            assert!(denominator != 0, "denominator == 0");
+            return Rational::from_big(BigInt::from(numerator), BigInt::from(denominator));
+        }
+
+        Rational(Repr::Small(Ratio::new(numerator, denominator)))
+    }
+
+    pub fn from_big(numerator: BigInt, denominator: BigInt) -> Rational {
+        Rational(Repr::Big(BigRational::new(numerator, denominator))).shrink()
+    }
+
+    pub fn zero() -> Rational {
+        Rational::from(0)
+    }
+
+    pub fn one() -> Rational {
+        Rational::from(1)
+    }
+
+    fn to_big(&self) -> BigRational {
+        match &self.0 {
+            Repr::Small(value) => {
+                BigRational::new_raw(BigInt::from(*value.numer()), BigInt::from(*value.denom()))
+            }
+            Repr::Big(value) => value.clone(),
+        }
+    }
+
+    /// Moves a big value back to `i64` when both parts fit.
+    fn shrink(self) -> Rational {
+        match self.0 {
+            Repr::Big(value) => match (value.numer().to_i64(), value.denom().to_i64()) {
+                (Some(numerator), Some(denominator)) => {
+                    Rational(Repr::Small(Ratio::new_raw(numerator, denominator)))
+                }
+                _ => Rational(Repr::Big(value)),
+            },
+            small => Rational(small),
+        }
+    }
+
+    fn apply<S, B>(&self, other: &Rational, small: S, big: B) -> Rational
+    where
+        S: Fn(&Ratio<i64>, &Ratio<i64>) -> Option<Ratio<i64>>,
+        B: Fn(BigRational, BigRational) -> BigRational,
+    {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &other.0) {
+            if let Some(result) = small(a, b) {
+                return Rational(Repr::Small(result));
+            }
+        }
+
+        Rational(Repr::Big(big(self.to_big(), other.to_big()))).shrink()
+    }
+
+    pub fn numerator(&self) -> BigInt {
+        match &self.0 {
+            Repr::Small(value) => BigInt::from(*value.numer()),
+            Repr::Big(value) => value.numer().clone(),
+        }
+    }
+
+    pub fn denominator(&self) -> BigInt {
+        match &self.0 {
+            Repr::Small(value) => BigInt::from(*value.denom()),
+            Repr::Big(value) => value.denom().clone(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        matches!(&self.0, Repr::Small(value) if value.is_zero())
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match &self.0 {
+            Repr::Small(value) => value.is_integer(),
+            Repr::Big(value) => value.is_integer(),
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match &self.0 {
+            Repr::Small(value) => value.is_negative(),
+            Repr::Big(value) => value.is_negative(),
+        }
+    }
+
+    pub fn abs(&self) -> Rational {
+        if self.is_negative() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The value as a whole number, or `None` if it has a fractional part.
+    pub fn to_integer(&self) -> Option<BigInt> {
+        if self.is_integer() {
+            Some(self.numerator())
+        } else {
+            None
+        }
+    }
+
+    /// The value as an `i64`, or `None` if it has a fractional part or is out of range.
+    pub fn to_i64(&self) -> Option<i64> {
+        match &self.0 {
+            Repr::Small(value) if value.is_integer() => Some(*value.numer()),
+            _ => None,
+        }
+    }
+
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        if other.is_zero() {
+            None
+        } else {
+            Some(self.clone() / other.clone())
+        }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational(Repr::Small(Ratio::from_integer(value)))
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(value: BigInt) -> Self {
+        Rational::from_big(value, BigInt::one())
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.apply(&rhs, |a, b| a.checked_add(b), |a, b| a + b)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.apply(&rhs, |a, b| a.checked_sub(b), |a, b| a - b)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.apply(&rhs, |a, b| a.checked_mul(b), |a, b| a * b)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics when dividing by zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "division by zero");
+        self.apply(&rhs, |a, b| a.checked_div(b), |a, b| a / b)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        Rational::zero() - self
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Repr::Small(a), Repr::Small(b)) => a.cmp(b),
+            _ => self.to_big().cmp(&other.to_big()),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Repr::Small(value) => write!(f, "{}", value),
+            Repr::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ParseRationalError {
+    Part(ParseBigIntError),
+    ZeroDenominator,
+}
+
+impl Display for ParseRationalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRationalError::Part(err) => write!(f, "{}", err),
+            ParseRationalError::ZeroDenominator => write!(f, "denominator can't be zero"),
+        }
+    }
+}
+
+impl Error for ParseRationalError {}
+
+impl FromStr for Rational {
+    type Err = ParseRationalError;
+
+    /// Parses either a whole number or `numerator/denominator`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+        let numerator: BigInt = numerator.trim().parse().map_err(ParseRationalError::Part)?;
+        let denominator: BigInt = denominator.trim().parse().map_err(ParseRationalError::Part)?;
+
+        if denominator.is_zero() {
+            Err(ParseRationalError::ZeroDenominator)
+        } else {
+            Ok(Rational::from_big(numerator, denominator))
+        }
+    }
+}
+
+/// Solves `coefficients * x = constants` by Gauss-Jordan elimination, returning `None` unless the
+/// system is square with exactly one solution.
+pub fn solve_linear_system(
+    coefficients: &[Vec<Rational>],
+    constants: &[Rational],
+) -> Option<Vec<Rational>> {
+    let size = constants.len();
+    if coefficients.len() != size || coefficients.iter().any(|row| row.len() != size) {
+        return None;
+    }
+
+    let mut rows: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, constant)| row.iter().chain([constant]).cloned().collect())
+        .collect();
+
+    for col in 0..size {
+        let pivot = (col..size).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+
+        let divisor = rows[col][col].clone();
+        for value in rows[col].iter_mut() {
+            *value = value.clone() / divisor.clone();
+        }
+
+        let pivot_row = rows[col].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index == col || row[col].is_zero() {
+                continue;
+            }
+
+            let factor = row[col].clone();
+            for (value, pivot) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = value.clone() - factor.clone() * pivot.clone();
+            }
+        }
+    }
+
+    Some(rows.into_iter().map(|mut row| row.pop().unwrap()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(value: &str) -> Rational {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn lowest_terms() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(1, 3) + Rational::new(1, 6), Rational::new(1, 2));
+        assert_eq!(Rational::new(2, 3) * Rational::new(3, 2), Rational::one());
+        assert_eq!(Rational::new(1, 2) / Rational::new(1, 4), Rational::from(2));
+        assert_eq!(Rational::new(-3, 4).to_string(), "-3/4");
+        assert_eq!(Rational::from(5).to_string(), "5");
+    }
+
+    #[test]
+    fn signed_minimum() {
+        let flipped = Rational::new(i64::MIN, -1);
+        assert!(matches!(flipped.0, Repr::Big(_)));
+        assert_eq!(flipped, big("9223372036854775808"));
+
+        assert_eq!(Rational::new(i64::MIN, 1), Rational::from(i64::MIN));
+        assert!(matches!(Rational::new(i64::MIN, 2).0, Repr::Small(_)));
+        assert_eq!(Rational::new(i64::MIN, 2), Rational::from(i64::MIN / 2));
+        assert_eq!(Rational::new(3, i64::MIN), big("-3/9223372036854775808"));
+        assert_eq!(Rational::new(i64::MIN, i64::MIN), Rational::one());
+    }
+
+    #[test]
+    fn promotes_to_big_and_back() {
+        let max = Rational::from(i64::MAX);
+        let above = max.clone() + Rational::one();
+        assert!(matches!(above.0, Repr::Big(_)));
+        assert_eq!(above, big("9223372036854775808"));
+        assert_eq!(above.to_i64(), None);
+        assert_eq!(above.to_integer(), Some(BigInt::from(i64::MAX) + 1));
+
+        let back = above - Rational::one();
+        assert!(matches!(back.0, Repr::Small(_)));
+        assert_eq!(back.to_i64(), Some(i64::MAX));
+
+        let squared = max.clone() * max.clone();
+        assert!(matches!(squared.0, Repr::Big(_)));
+        assert_eq!(squared / max, Rational::from(i64::MAX));
+
+        let tiny = Rational::new(1, i64::MAX) * Rational::new(1, 2);
+        assert!(matches!(tiny.0, Repr::Big(_)));
+        assert_eq!(tiny * Rational::from(2), Rational::new(1, i64::MAX));
+
+        assert_eq!(-Rational::from(i64::MIN), big("9223372036854775808"));
+    }
+
+    #[test]
+    fn comparisons_across_sizes() {
+        let huge = big("100000000000000000000/3");
+        assert!(huge > Rational::from(i64::MAX));
+        assert!(-huge.clone() < Rational::from(i64::MIN));
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert!(!huge.is_negative());
+        assert_eq!((-huge.clone()).abs(), huge);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(big("6/4"), Rational::new(3, 2));
+        assert_eq!(big(" -7 / 2 "), Rational::new(-7, 2));
+        assert_eq!(big("12"), Rational::from(12));
+        assert!(matches!("1/0".parse::<Rational>(), Err(ParseRationalError::ZeroDenominator)));
+        assert!(matches!("1/x".parse::<Rational>(), Err(ParseRationalError::Part(_))));
+    }
+
+    #[test]
+    fn integers_and_division() {
+        assert!(Rational::new(4, 2).is_integer());
+        assert_eq!(Rational::new(1, 2).to_integer(), None);
+        assert_eq!(Rational::one().checked_div(&Rational::zero()), None);
+        assert_eq!(Rational::one().checked_div(&Rational::from(4)), Some(Rational::new(1, 4)));
+    }
+
+    fn system(rows: &[&[i64]]) -> (Vec<Vec<Rational>>, Vec<Rational>) {
+        rows.iter()
+            .map(|row| {
+                let (constant, coefficients) = row.split_last().unwrap();
+                (coefficients.iter().map(|&c| Rational::from(c)).collect(), Rational::from(*constant))
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let (coefficients, constants) = system(&[&[1, 1, 1, 6], &[0, 2, 5, -4], &[2, 5, -1, 27]]);
+        let solution = solve_linear_system(&coefficients, &constants).unwrap();
+        assert_eq!(solution, [Rational::from(5), Rational::from(3), Rational::from(-2)]);
+
+        // the first pivot is zero, so rows need swapping, and the answer is fractional
+        let (coefficients, constants) = system(&[&[0, 2, 1], &[3, 1, 2]]);
+        let solution = solve_linear_system(&coefficients, &constants).unwrap();
+        assert_eq!(solution, [Rational::new(1, 2), Rational::new(1, 2)]);
+    }
+
+    #[test]
+    fn rejects_systems_without_a_single_solution() {
+        let (coefficients, constants) = system(&[&[1, 2, 3], &[2, 4, 6]]);
+        assert_eq!(solve_linear_system(&coefficients, &constants), None);
+
+        let (coefficients, constants) = system(&[&[1, 2, 3]]);
+        assert_eq!(solve_linear_system(&coefficients, &constants), None);
+    }
+}
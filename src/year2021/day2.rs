@@ -1,8 +1,10 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::direction::Direction4;
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::point::Point;
 use std::error;
 use std::fmt;
 use std::fmt::Formatter;
-use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone)]
 struct DirectionParseError;
@@ -15,82 +17,106 @@ impl fmt::Display for DirectionParseError {
 
 impl error::Error for DirectionParseError {}
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Forward,
-}
-
-impl FromStr for Direction {
-    type Err = DirectionParseError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "up" => Ok(Direction::Up),
-            "down" => Ok(Direction::Down),
-            "forward" => Ok(Direction::Forward),
-            _ => Err(DirectionParseError),
-        }
+/// The submarine only moves forward, so that is east with depth increasing down the y axis.
+fn parse_direction(input: &str) -> Result<Direction4, DirectionParseError> {
+    match input {
+        "forward" => Ok(Direction4::East),
+        "up" => Ok(Direction4::North),
+        "down" => Ok(Direction4::South),
+        _ => Err(DirectionParseError),
     }
 }
 
 #[derive(Debug, Copy, Clone)]
 struct MoveInstruction {
-    direction: Direction,
-    length: u64,
+    direction: Direction4,
+    length: i64,
 }
 
-pub fn run(input: &str) -> ChallengeResult {
-    let input: Result<Vec<MoveInstruction>, Box<dyn error::Error>> = input
-        .lines()
-        .map(|line| {
-            let direction = Direction::from_str(&line[..line.len() - 2])?;
-            let length: u64 = line[line.len() - 1..].parse()?;
+impl MoveInstruction {
+    fn offset(&self) -> Point<i64> {
+        let Point { x, y } = self.direction.to_point_y_down::<i64>();
+        Point::new(x * self.length, y * self.length)
+    }
+}
 
-            Ok(MoveInstruction { direction, length })
+fn parse_instructions(input: &str) -> Result<Vec<MoveInstruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (direction, length) = line
+                .split_once(' ')
+                .ok_or("expected a direction and a distance")
+                .on_line(i, line)?;
+
+            Ok(MoveInstruction {
+                direction: parse_direction(direction).at(i, line, direction)?,
+                length: length.parse().at(i, line, length)?,
+            })
         })
-        .collect();
+        .collect()
+}
 
-    let input = input?;
+pub fn run(input: &str) -> ChallengeResult {
+    let input = parse_instructions(input)?;
 
     Ok(Solution::from(part1(&input), part2(&input)))
 }
 
-fn part1(input: &[MoveInstruction]) -> u64 {
-    use Direction::*;
+fn part1(input: &[MoveInstruction]) -> i64 {
+    let position = input
+        .iter()
+        .fold(Point::new(0, 0), |position, instruction| position + instruction.offset());
 
-    let mut horizontal: u64 = 0;
-    let mut depth: u64 = 0;
+    position.x * position.y
+}
+
+fn part2(input: &[MoveInstruction]) -> i64 {
+    let mut horizontal: i64 = 0;
+    let mut depth: i64 = 0;
+    let mut aim: i64 = 0;
+
+    for instruction in input {
+        let offset = instruction.offset();
 
-    for MoveInstruction { direction, length } in input {
-        match direction {
-            Up => depth -= length,
-            Down => depth += length,
-            Forward => horizontal += length,
-        }
+        // up and down only turn the submarine, forward moves it along its aim
+        aim += offset.y;
+        horizontal += offset.x;
+        depth += aim * offset.x;
     }
 
     horizontal * depth
 }
 
-fn part2(input: &[MoveInstruction]) -> u64 {
-    use Direction::*;
-
-    let mut horizontal: u64 = 0;
-    let mut depth: u64 = 0;
-    let mut aim: u64 = 0;
-
-    for MoveInstruction { direction, length } in input {
-        match direction {
-            Up => aim -= length,
-            Down => aim += length,
-            Forward => {
-                horizontal += length;
-                depth += aim * length;
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (150.into(), 900.into()));
     }
 
-    horizontal * depth
+    #[test]
+    fn multi_digit_distances() {
+        let solution = run("forward 10\ndown 12\nforward 3\n").unwrap();
+        assert_eq!((solution.part1, solution.part2), (156.into(), 468.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_instructions("forward 5\nsideways 3\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert!(error.to_string().ends_with("2 | sideways 3\n  | ^^^^^^^^"), "{}", error);
+
+        let error = parse_instructions("up 1x").unwrap_err();
+        assert!(error.to_string().ends_with("1 | up 1x\n  |    ^^"), "{}", error);
+
+        let error = parse_instructions("down").unwrap_err();
+        assert!(error.to_string().ends_with("1 | down\n  | ^^^^"), "{}", error);
+    }
 }
@@ -1,44 +1,60 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
 
-pub fn run(input: &str) -> ChallengeResult {
-    let input = input
+/// The diagnostic numbers and how many bits each of them has.
+fn parse_report(input: &str) -> Result<(Vec<u16>, u16), ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+
+    let numbers = input
         .lines()
-        .map(|line| u16::from_str_radix(line, 2).unwrap())
-        .collect::<Vec<_>>();
-    Ok(Solution::from(part1(&input), part2(&input)))
+        .enumerate()
+        .map(|(i, line)| {
+            if line.len() != width {
+                return Err(format!("expected {} bits like the first line", width)).on_line(i, line);
+            }
+            u16::from_str_radix(line, 2).on_line(i, line)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((numbers, width as u16))
+}
+
+pub fn run(input: &str) -> ChallengeResult {
+    let (input, width) = parse_report(input)?;
+    Ok(Solution::from(part1(&input, width), part2(&input, width)))
 }
 
 fn count_bits(input: &[u16], position: u16) -> u16 {
     input.iter().map(|x| (x >> position) & 1).sum()
 }
 
-fn part1(input: &[u16]) -> u64 {
+fn part1(input: &[u16], width: u16) -> u64 {
     let length = input.len() as u16;
 
     let mut gamma = 0;
-    for position in 0..12 {
+    for position in 0..width {
         let count = count_bits(input, position);
         if count >= length - count {
             gamma |= 1 << position;
         }
     }
 
-    let epsilon = !gamma & 0b111111111111;
+    let epsilon = !gamma & ((1 << width) - 1);
 
     gamma * epsilon
 }
 
-fn part2(input: &[u16]) -> u64 {
-    let ogr = life_support(input, true) as u64;
-    let co2 = life_support(input, false) as u64;
+fn part2(input: &[u16], width: u16) -> u64 {
+    let ogr = life_support(input, width, true) as u64;
+    let co2 = life_support(input, width, false) as u64;
 
     ogr * co2
 }
 
-fn life_support(input: &[u16], use_most_common_bit: bool) -> u16 {
+fn life_support(input: &[u16], width: u16, use_most_common_bit: bool) -> u16 {
     let mut temp = Vec::from(input);
 
-    for position in (0..12).rev() {
+    for position in (0..width).rev() {
         let length = temp.len() as u16;
         let bit_count = count_bits(&temp, position);
 
@@ -56,3 +72,39 @@ fn life_support(input: &[u16], use_most_common_bit: bool) -> u16 {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (198.into(), 230.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_report("00100\n11120\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert!(error.to_string().ends_with("2 | 11120\n  | ^^^^^"), "{}", error);
+
+        let error = parse_report("00100\n1111\n").unwrap_err();
+        assert!(error.to_string().starts_with("expected 5 bits like the first line on line 2"));
+    }
+}
@@ -1,36 +1,25 @@
 use crate::challenge_result::{ChallengeResult, Solution};
-
-#[derive(Debug, Copy, Clone)]
-struct Point {
-    x: i16,
-    y: i16,
-}
-
-impl Point {
-    fn from_str(input: &str) -> Self {
-        let mut iter = input.split(',').flat_map(str::parse);
-
-        Self {
-            x: iter.next().unwrap(),
-            y: iter.next().unwrap(),
-        }
-    }
-}
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::point::Point;
+use crate::year2022::rect::Rect;
 
 #[derive(Debug, Copy, Clone)]
 struct Line {
-    from: Point,
-    to: Point,
+    from: Point<i16>,
+    to: Point<i16>,
 }
 
 impl Line {
-    fn from_str(input: &str) -> Self {
-        let mut iter = input.split(" -> ").map(Point::from_str);
-
-        Self {
-            from: iter.next().unwrap(),
-            to: iter.next().unwrap(),
-        }
+    fn parse(index: usize, line: &str) -> Result<Self, ParseError> {
+        let (from, to) = line
+            .split_once(" -> ")
+            .ok_or("expected \"x,y -> x,y\"")
+            .on_line(index, line)?;
+
+        Ok(Self {
+            from: from.parse().at(index, line, from)?,
+            to: to.parse().at(index, line, to)?,
+        })
     }
 
     fn not_diagonal(&self) -> bool {
@@ -60,46 +49,34 @@ impl Board {
     }
 
     fn draw(&mut self, line: &Line) {
-        let dx = compare(line.from.x, line.to.x);
-        let dy = compare(line.from.y, line.to.y);
-
-        let mut x = line.from.x;
-        let mut y = line.from.y;
-
-        while x != line.to.x || y != line.to.y {
-            let index = self.index(x, y);
+        // an i16 always fits in the 64 bits lines allow, so `line_to` never fails here
+        for point in line.from.line_to(line.to).into_iter().flatten() {
+            let index = self.index(point.x, point.y);
             self.data[index] += 1;
-            x += dx;
-            y += dy;
         }
-
-        let index = self.index(x, y);
-        self.data[index] += 1;
-    }
-}
-
-fn compare(a: i16, b: i16) -> i16 {
-    if a < b {
-        1
-    } else if a > b {
-        -1
-    } else {
-        0
     }
 }
 
 pub fn run(input: &str) -> ChallengeResult {
     let input: Vec<Line> = input
         .lines()
-        .filter(|&line| !line.is_empty())
-        .map(Line::from_str)
-        .collect();
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| Line::parse(i, line))
+        .collect::<Result<_, _>>()?;
 
     Ok(Solution::from(part1(&input), part2(&input)))
 }
 
+fn board_for(input: &[Line]) -> Board {
+    let bounds = Rect::from_points(input.iter().flat_map(|line| [line.from, line.to]))
+        .unwrap_or(Rect::new(Point::new(0, 0), Point::new(0, 0)));
+
+    Board::new(bounds.max.x as usize + 1, bounds.max.y as usize + 1)
+}
+
 fn part1(input: &[Line]) -> u64 {
-    let mut board = Board::new(1000, 1000);
+    let mut board = board_for(input);
     for line in input {
         if line.not_diagonal() {
             board.draw(line);
@@ -110,10 +87,44 @@ fn part1(input: &[Line]) -> u64 {
 }
 
 fn part2(input: &[Line]) -> u64 {
-    let mut board = Board::new(1000, 1000);
+    let mut board = board_for(input);
     for line in input {
         board.draw(line);
     }
 
     board.count_doubles() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (5.into(), 12.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = Line::parse(3, "0,9 -> 5;9").unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 8));
+        assert!(error.to_string().ends_with("4 | 0,9 -> 5;9\n  |        ^^^"), "{}", error);
+
+        let error = Line::parse(0, "0,9 - 5,9").unwrap_err();
+        assert!(error.to_string().ends_with("1 | 0,9 - 5,9\n  | ^^^^^^^^^"), "{}", error);
+    }
+}
@@ -1,43 +1,158 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::Context;
+use crate::year2022::matrix::Matrix;
+use std::error;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Timers {
+    reset: usize,
+    newborn: usize,
+}
+
+const TIMERS: Timers = Timers { reset: 6, newborn: 8 };
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum FishError {
+    /// Fish that reset to a timer above the newborn one would have no row to go to.
+    ResetAfterNewborn(Timers),
+    /// There has to be a count for every timer from zero up to the newborn one.
+    WrongTimerCount { expected: usize, found: usize },
+    Overflow { days: u64 },
+}
+
+impl fmt::Display for FishError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FishError::ResetAfterNewborn(timers) => write!(
+                f,
+                "The reset timer {} can't be above the newborn timer {}",
+                timers.reset, timers.newborn
+            ),
+            FishError::WrongTimerCount { expected, found } => {
+                write!(f, "Expected fish counts for {} timers, got {}", expected, found)
+            }
+            FishError::Overflow { days } => {
+                write!(f, "The fish count after {} days doesn't fit in a u64, pass a modulus", days)
+            }
+        }
+    }
+}
+
+impl error::Error for FishError {}
 
 pub fn run(input: &str) -> ChallengeResult {
-    let first_line = input.lines().next().unwrap();
+    let first_line = input.lines().next().ok_or("expected a line of fish timers")?;
 
-    let mut fish = [0u64; 9];
-    for fish_age in first_line.split(',').map(|num| num.parse::<usize>().unwrap()) {
-        fish[fish_age] += 1;
+    let mut fish = vec![0u64; TIMERS.newborn + 1];
+    for num in first_line.split(',') {
+        let fish_age: usize = num.parse().at(0, first_line, num)?;
+        let count = fish
+            .get_mut(fish_age)
+            .ok_or_else(|| format!("timer can't be above {}", TIMERS.newborn))
+            .at(0, first_line, num)?;
+        *count += 1;
     }
 
-    Ok(Solution::from(part1(&fish), part2(&fish)))
+    Ok(Solution::from(
+        count_fish(&fish, 80, TIMERS, None)?,
+        count_fish(&fish, 256, TIMERS, None)?,
+    ))
 }
 
-fn part1(fish: &[u64; 9]) -> u64 {
-    let mut fish = *fish;
+/// One day of spawning as a matrix: every timer counts down, and fish at zero reset themselves
+/// and each add a newborn.
+fn transition(timers: Timers) -> Result<Matrix<u64>, FishError> {
+    if timers.reset > timers.newborn {
+        return Err(FishError::ResetAfterNewborn(timers));
+    }
+
+    let mut matrix = Matrix::zero(timers.newborn + 1, timers.newborn + 1);
 
-    for _ in 0..80 {
-        run_simulation(&mut fish);
+    for timer in 0..timers.newborn {
+        matrix[(timer, timer + 1)] = 1;
     }
+    matrix[(timers.reset, 0)] += 1;
+    matrix[(timers.newborn, 0)] += 1;
 
-    fish.iter().sum::<u64>()
+    Ok(matrix)
 }
 
-fn part2(fish: &[u64; 9]) -> u64 {
-    let mut fish = *fish;
-
-    for _ in 0..256 {
-        run_simulation(&mut fish);
+/// Counts the fish after any number of days, with the count reduced by `modulus` if one is
+/// given since the exact count outgrows a u64 after a few hundred days. `fish` holds the number
+/// of fish at each timer from zero up to `timers.newborn`.
+fn count_fish(
+    fish: &[u64],
+    days: u64,
+    timers: Timers,
+    modulus: Option<u64>,
+) -> Result<u64, FishError> {
+    let transition = transition(timers)?;
+    if fish.len() != timers.newborn + 1 {
+        return Err(FishError::WrongTimerCount {
+            expected: timers.newborn + 1,
+            found: fish.len(),
+        });
     }
+    let fish = Matrix::column(fish);
+
+    let counts = match modulus {
+        Some(modulus) => Some(transition.pow_mod(days, modulus).mul_mod(&fish, modulus)),
+        None => transition
+            .checked_pow(days)
+            .and_then(|days| days.checked_mul(&fish)),
+    };
+
+    let total = counts.and_then(|counts| match modulus {
+        Some(modulus) => Some(counts.iter().fold(0, |total, &count| (total + count) % modulus)),
+        None => counts.iter().try_fold(0u64, |total, &count| total.checked_add(count)),
+    });
 
-    fish.iter().sum::<u64>()
+    total.ok_or(FishError::Overflow { days })
 }
 
-fn run_simulation(fish: &mut [u64; 9]) {
-    let new_fish = fish[0];
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for i in 0..fish.len() - 1 {
-        fish[i] = fish[i + 1];
+    /// Timer counts for the example school of fish `3,4,3,1,2`.
+    const EXAMPLE: [u64; 9] = [0, 1, 1, 2, 1, 0, 0, 0, 0];
+
+    #[test]
+    fn example() {
+        assert_eq!(count_fish(&EXAMPLE, 18, TIMERS, None).unwrap(), 26);
+        assert_eq!(count_fish(&EXAMPLE, 80, TIMERS, None).unwrap(), 5934);
+        assert_eq!(count_fish(&EXAMPLE, 256, TIMERS, None).unwrap(), 26_984_457_539);
+
+        let solution = run("3,4,3,1,2\n").unwrap();
+        assert_eq!((solution.part1, solution.part2), (5934.into(), 26_984_457_539u64.into()));
     }
 
-    fish[6] += new_fish;
-    fish[8] = new_fish;
-}
\ No newline at end of file
+    #[test]
+    fn far_horizons() {
+        assert!(count_fish(&EXAMPLE, 1_000_000_000_000, TIMERS, None).is_err());
+
+        let modulus = 1_000_000_007;
+        let exact = count_fish(&EXAMPLE, 256, TIMERS, None).unwrap();
+        assert_eq!(count_fish(&EXAMPLE, 256, TIMERS, Some(modulus)).unwrap(), exact % modulus);
+        assert!(count_fish(&EXAMPLE, 1_000_000_000_000, TIMERS, Some(modulus)).is_ok());
+    }
+
+    #[test]
+    fn other_timers() {
+        // with no delay for newborns every fish doubles each day it reaches zero
+        let quick = Timers { reset: 1, newborn: 1 };
+        assert_eq!(count_fish(&[1, 0], 4, quick, None), Ok(4));
+
+        let backwards = Timers { reset: 8, newborn: 6 };
+        assert_eq!(
+            count_fish(&EXAMPLE[..7], 18, backwards, None),
+            Err(FishError::ResetAfterNewborn(backwards))
+        );
+        assert_eq!(
+            count_fish(&EXAMPLE[..7], 18, TIMERS, None),
+            Err(FishError::WrongTimerCount { expected: 9, found: 7 })
+        );
+    }
+}
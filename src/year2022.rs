@@ -1,7 +1,13 @@
-mod point;
-mod grid;
+pub mod point;
+pub mod grid;
+pub mod rect;
+pub mod interval;
+pub mod polygon;
 mod math;
-mod error;
+pub mod cycle;
+pub mod matrix;
+pub mod numeral;
+pub mod ocr;
 
 mod day1;
 mod day2;
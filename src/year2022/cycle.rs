@@ -0,0 +1,260 @@
+#![allow(dead_code)]
+
+use hashbrown::hash_map::Entry;
+use hashbrown::HashMap;
+use std::hash::Hash;
+use num::PrimInt;
+
+/// A sequence that settles into a loop: the states at steps `start` and `start + length` are
+/// the first pair to match.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state at step `n` given every state up to the end of the first loop.
+    pub fn state_at<'a, S>(&self, history: &'a [S], n: usize) -> &'a S {
+        &history[self.equivalent_step(n)]
+    }
+
+    /// Extrapolates a running total to step `n`, e.g. the height of a tower, when each loop
+    /// adds the same amount. `value_at` is only called for steps up to `start + length`.
+    pub fn extrapolate<T, F>(&self, n: usize, value_at: F) -> T
+    where
+        T: PrimInt,
+        F: Fn(usize) -> T,
+    {
+        if n < self.start {
+            return value_at(n);
+        }
+
+        let loops = T::from((n - self.start) / self.length).unwrap();
+        let per_loop = value_at(self.start + self.length) - value_at(self.start);
+
+        value_at(self.equivalent_step(n)) + loops * per_loop
+    }
+}
+
+/// Floyd's tortoise and hare. Only keeps two states in memory but steps the sequence about three
+/// times as often as the cycle is long.
+pub fn floyd<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Finds the length first with power-of-two sized windows, which usually
+/// needs fewer steps than Floyd's.
+pub fn brent<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Records the step each key was first seen at, for simulations where only part of the state
+/// (e.g. positions modulo some period) decides what happens next.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    first_seen: HashMap<K, usize>,
+    steps: usize,
+}
+
+impl<K: Hash + Eq> CycleDetector<K> {
+    pub fn new() -> CycleDetector<K> {
+        CycleDetector {
+            first_seen: HashMap::new(),
+            steps: 0,
+        }
+    }
+
+    /// Records the key of the next step, returning the cycle once a key repeats.
+    pub fn observe(&mut self, key: K) -> Option<Cycle> {
+        let step = self.steps;
+        self.steps += 1;
+
+        match self.first_seen.entry(key) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                Some(Cycle { start, length: step - start })
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(step);
+                None
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq> Default for CycleDetector<K> {
+    fn default() -> Self {
+        CycleDetector::new()
+    }
+}
+
+/// Runs the simulation until a state's key repeats, returning the cycle along with every state
+/// from the initial one up to the end of the first loop.
+pub fn detect_by_key<S, K, F, G>(initial: S, mut step: F, key: G) -> (Cycle, Vec<S>)
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+{
+    let mut detector = CycleDetector::new();
+    let mut history = vec![initial];
+
+    loop {
+        let current = history.last().unwrap();
+        if let Some(cycle) = detector.observe(key(current)) {
+            history.pop();
+            return (cycle, history);
+        }
+
+        let next = step(current);
+        history.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo-random steps through `0..modulus`, which must eventually loop.
+    fn step(modulus: u64) -> impl Fn(&u64) -> u64 {
+        move |&x| (x * x + 7) % modulus
+    }
+
+    /// The cycle found by remembering every state.
+    fn brute_force(initial: u64, modulus: u64) -> Cycle {
+        let step = step(modulus);
+        let mut seen = vec![initial];
+        loop {
+            let next = step(seen.last().unwrap());
+            if let Some(start) = seen.iter().position(|&state| state == next) {
+                return Cycle { start, length: seen.len() - start };
+            }
+            seen.push(next);
+        }
+    }
+
+    #[test]
+    fn floyd_and_brent_agree() {
+        for modulus in [2, 3, 10, 97, 1000, 65_537] {
+            for initial in [0, 1, 5, 42] {
+                let expected = brute_force(initial, modulus);
+                assert_eq!(floyd(initial, step(modulus)), expected, "floyd {} {}", initial, modulus);
+                assert_eq!(brent(initial, step(modulus)), expected, "brent {} {}", initial, modulus);
+            }
+        }
+    }
+
+    #[test]
+    fn pure_loop_starts_immediately() {
+        let rotate = |&x: &u32| (x + 1) % 5;
+        assert_eq!(floyd(0, rotate), Cycle { start: 0, length: 5 });
+        assert_eq!(brent(0, rotate), Cycle { start: 0, length: 5 });
+        assert_eq!(floyd(3, |&x: &u32| x), Cycle { start: 0, length: 1 });
+    }
+
+    #[test]
+    fn keyed_detection() {
+        // the key only sees the state modulo 4, so the loop shows up before the states repeat
+        let (cycle, history) = detect_by_key(10u64, |&x| x - 2, |&x| x % 4);
+        assert_eq!(history, [10, 8]);
+        assert_eq!(cycle, Cycle { start: 0, length: 2 });
+
+        let (cycle, history) = detect_by_key(0u64, step(1000), |&x| x);
+        assert_eq!(cycle, brute_force(0, 1000));
+        assert_eq!(history.len(), cycle.start + cycle.length);
+    }
+
+    #[test]
+    fn detector_reports_the_first_repeat() {
+        let mut detector = CycleDetector::new();
+        let keys = ['a', 'b', 'c', 'd', 'b'];
+        let cycles: Vec<Option<Cycle>> = keys.iter().map(|&key| detector.observe(key)).collect();
+        assert_eq!(cycles, [None, None, None, None, Some(Cycle { start: 1, length: 3 })]);
+    }
+
+    #[test]
+    fn looking_up_and_extrapolating() {
+        let cycle = Cycle { start: 2, length: 3 };
+        let history = ['a', 'b', 'c', 'd', 'e'];
+        assert_eq!(cycle.equivalent_step(1), 1);
+        assert_eq!(cycle.equivalent_step(5), 2);
+        assert_eq!(*cycle.state_at(&history, 1_000_000), 'e');
+        assert_eq!(*cycle.state_at(&history, 1_000_001), 'c');
+
+        // each loop adds 1, 2 and then 3 to the running total
+        let totals = [0u64, 1, 2, 3, 5, 8];
+        let value_at = |n: usize| totals[n];
+        for (n, &total) in totals.iter().enumerate() {
+            assert_eq!(cycle.extrapolate(n, value_at), total);
+        }
+        assert_eq!(cycle.extrapolate(8, value_at), 14);
+        assert_eq!(cycle.extrapolate(2 + 3 * 1_000_000, value_at), 2 + 6 * 1_000_000);
+    }
+}
@@ -1,8 +1,10 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use crate::challenge_result::{ChallengeResult, Solution};
-use crate::year2022::error::ParseLineError;
+use crate::challenge_result::{Answer, ChallengeResult, Solution};
+use crate::parse_error::parse_lines;
+use crate::year2022::grid::Grid;
+use crate::year2022::ocr;
 
 #[derive(Debug, Copy, Clone)]
 enum Command {
@@ -25,13 +27,12 @@ impl FromStr for Command {
     type Err = ParseCommandError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (command, arg) = s.split_at(4);
+        let (command, arg) = s.split_once(' ').unwrap_or((s, ""));
 
         match command {
             "noop" => Ok(Command::NoOp),
             "addx" => Ok(Command::AddX(
-                arg.trim_start()
-                    .parse()
+                arg.parse()
                     .map_err(|_| ParseCommandError)?
             )),
             _ => Err(ParseCommandError),
@@ -92,33 +93,239 @@ fn part1(x_values: &[i32]) -> i32 {
         .sum()
 }
 
-fn part2(x_values: &[i32]) {
-    let mut iter = x_values.iter();
+/// The letters on the screen, or the screen itself when they can't be read.
+fn part2(x_values: &[i32]) -> Answer {
+    let mut screen = Grid::new(40, 6, || false);
 
-    for _ in 0..6 {
-        for col in 0..40 {
-            if let Some(&x) = iter.next() {
-                if col >= x - 1 && col <= x + 1 {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-        }
-        println!();
+    for (pixel, (&x, col)) in screen.iter_mut().zip(x_values.iter().zip((0..40).cycle())) {
+        *pixel = col >= x - 1 && col <= x + 1;
+    }
+
+    match ocr::recognize(&screen) {
+        Ok(letters) => Answer::Text(letters),
+        Err(_) => Answer::MultiLine(render(&screen)),
     }
 }
 
+fn render(screen: &Grid<bool>) -> String {
+    (0..screen.height)
+        .map(|row| {
+            let line: String = screen
+                .iter_full_row(row)
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect();
+            line + "\n"
+        })
+        .collect()
+}
+
 pub fn run(input: &str) -> ChallengeResult {
-    let commands: Vec<Command> = input.lines()
-        .enumerate()
-        .map(|(i, line)|
-            line.parse::<Command>().map_err(|error| ParseLineError::new(i, error))
-        ).collect::<Result<_, _>>()?;
+    let commands: Vec<Command> = parse_lines(input)?;
 
     let x_values: Vec<i32> = Simulation::new(&commands).collect();
 
-    part2(&x_values);
+    Ok(Solution::from(part1(&x_values), part2(&x_values)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(Solution::from(part1(&x_values), 0))
+    const INPUT: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    /// Cycle by cycle register values that light exactly the `#` pixels of `picture`.
+    fn drawing(picture: &[&str]) -> Vec<i32> {
+        picture
+            .iter()
+            .flat_map(|row| format!("{:.<40}", row).into_bytes())
+            .zip((0..40).cycle())
+            .map(|(pixel, col)| if pixel == b'#' { col } else { -10 })
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let solution = run(INPUT).unwrap();
+        let screen = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+        assert_eq!(
+            (solution.part1, solution.part2),
+            (13140.into(), Answer::MultiLine(screen.to_string()))
+        );
+    }
+
+    #[test]
+    fn reads_letters() {
+        let x_values = drawing(&[
+            "#..#..###.",
+            "#..#...#..",
+            "####...#..",
+            "#..#...#..",
+            "#..#...#..",
+            "#..#..###.",
+        ]);
+        assert_eq!(part2(&x_values), Answer::Text("HI".to_string()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = run("noop\naddx five\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "command must be noop or addx on line 2, column 1\n2 | addx five\n  | ^^^^^^^^^"
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{check_skipped, Context, ParseError};
 use crate::year2022::math;
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::{Captures, Regex};
+use std::error::Error;
+use std::str::FromStr;
 
 type MonkeyItem = i64;
 
@@ -39,6 +42,14 @@ struct MonkeyTest {
     recipient_false: usize,
 }
 
+#[derive(Debug, Clone)]
+struct Troop {
+    monkeys: Vec<Monkey>,
+    items: Vec<Vec<MonkeyItem>>,
+    /// Worry levels can be reduced by this without changing any monkey's test.
+    least_common_multiple: MonkeyItem,
+}
+
 fn round(
     monkeys: &[Monkey],
     operation: Operation,
@@ -80,13 +91,14 @@ fn rounds(
 }
 
 pub fn run(input: &str) -> ChallengeResult {
-    let (monkeys, items) = parse_monkeys(input);
-
-    let least_common_multiple = monkeys
-        .iter()
-        .map(|monkey| monkey.test.divisible)
-        .reduce(math::lcm)
-        .unwrap();
+    let Troop {
+        monkeys,
+        items,
+        least_common_multiple,
+    } = parse_monkeys(input)?;
+    if monkeys.is_empty() {
+        return Err("expected at least one monkey".into());
+    }
 
     let part1 = rounds(&monkeys, &items, Operation::Div(3), 20);
     let part2 = rounds(&monkeys, &items, Operation::Mod(least_common_multiple), 10000);
@@ -94,7 +106,7 @@ pub fn run(input: &str) -> ChallengeResult {
     Ok(Solution::from(part1, part2))
 }
 
-fn parse_monkeys(s: &str) -> (Vec<Monkey>, Vec<Vec<MonkeyItem>>) {
+fn parse_monkeys(s: &str) -> Result<Troop, ParseError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(
             r"(?x)
@@ -109,46 +121,135 @@ fn parse_monkeys(s: &str) -> (Vec<Monkey>, Vec<Vec<MonkeyItem>>) {
         .unwrap();
     }
 
-    RE.captures_iter(s)
-        .map(|cap| {
-            let items: Vec<MonkeyItem> = cap
-                .get(1)
-                .unwrap()
+    let mut monkeys = Vec::new();
+    let mut items = Vec::new();
+    let mut recipients = Vec::new();
+    let mut least_common_multiple = 1;
+    let mut parsed_up_to = 0;
+    for cap in RE.captures_iter(s) {
+        let whole = cap.get(0).unwrap();
+        check_skipped(s, parsed_up_to..whole.start(), "expected a monkey")?;
+        parsed_up_to = whole.end();
+
+        let items_field = cap.get(1).unwrap();
+        items.push(
+            items_field
                 .as_str()
-                .split(", ")
-                .map(|v| v.parse::<MonkeyItem>().unwrap())
-                .collect();
+                .split(',')
+                .map(|v| v.trim().parse::<MonkeyItem>().at_offset(s, items_field.range()))
+                .collect::<Result<_, _>>()?,
+        );
+
+        let operation = if &cap[3] == "old" {
+            Operation::Square
+        } else if &cap[2] == "*" {
+            Operation::Mul(parse_group(s, &cap, 3)?)
+        } else {
+            Operation::Add(parse_group(s, &cap, 3)?)
+        };
+
+        let divisible = parse_group(s, &cap, 4)?;
+        let field = cap.get(4).unwrap();
+        if divisible == 0 {
+            return Err("can't test divisibility by zero").at_offset(s, field.range());
+        }
+        least_common_multiple = math::checked_lcm(least_common_multiple, divisible)
+            .ok_or("divisors have no common multiple small enough to reduce worry by")
+            .at_offset(s, field.range())?;
+
+        let recipient_true = parse_group(s, &cap, 5)?;
+        let recipient_false = parse_group(s, &cap, 6)?;
+        recipients.push([cap.get(5).unwrap(), cap.get(6).unwrap()]);
+
+        monkeys.push(Monkey {
+            operation,
+            test: MonkeyTest {
+                divisible,
+                recipient_true,
+                recipient_false,
+            },
+        });
+    }
+    check_skipped(s, parsed_up_to..s.len(), "expected a monkey")?;
+
+    // a monkey throwing to itself would keep catching the same items forever
+    for (idx, (monkey, fields)) in monkeys.iter().zip(recipients).enumerate() {
+        let targets = [monkey.test.recipient_true, monkey.test.recipient_false];
+        for (target, field) in targets.into_iter().zip(fields) {
+            if target == idx {
+                return Err("monkey can't throw to itself").at_offset(s, field.range());
+            }
+            if target >= monkeys.len() {
+                return Err("no such monkey").at_offset(s, field.range());
+            }
+        }
+    }
 
-            let operator_raw = cap.get(2).unwrap().as_str();
-            let operation_source_raw = cap.get(3).unwrap().as_str();
+    Ok(Troop {
+        monkeys,
+        items,
+        least_common_multiple,
+    })
+}
 
-            let operation = if operation_source_raw == "old" {
-                Operation::Square
-            } else {
-                let operation_source = operation_source_raw.parse::<MonkeyItem>().unwrap();
+/// Complains about anything but whitespace between monkeys, which the regex would otherwise
+/// silently step over.
+fn parse_group<T>(s: &str, cap: &Captures, group: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Error + 'static,
+{
+    let field = cap.get(group).unwrap();
+    field.as_str().parse().at_offset(s, field.range())
+}
 
-                if operator_raw == "*" {
-                    Operation::Mul(operation_source)
-                } else {
-                    Operation::Add(operation_source)
-                }
-            };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (10605.into(), 2713310158u64.into()));
+    }
 
-            let divisible: MonkeyItem = cap.get(4).unwrap().as_str().parse().unwrap();
-            let recipient_true: usize = cap.get(5).unwrap().as_str().parse().unwrap();
-            let recipient_false: usize = cap.get(6).unwrap().as_str().parse().unwrap();
-
-            (
-                Monkey {
-                    operation,
-                    test: MonkeyTest {
-                        divisible,
-                        recipient_true,
-                        recipient_false,
-                    },
-                },
-                items,
-            )
-        })
-        .unzip()
+    #[test]
+    fn reports_divisors_without_a_small_common_multiple() {
+        let input = EXAMPLE
+            .replace("divisible by 23", "divisible by 4294967291")
+            .replace("divisible by 19", "divisible by 4294967279");
+        let error = run(&input).unwrap_err().to_string();
+        assert!(error.starts_with("divisors have no common multiple"), "{}", error);
+        assert!(error.contains("on line 11, column 22"), "{}", error);
+    }
 }
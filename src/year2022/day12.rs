@@ -1,5 +1,11 @@
-use crate::challenge_result::{ChallengeResult, Solution};
+use std::collections::VecDeque;
+use crate::challenge_result::{Answer, ChallengeResult, Solution};
+use crate::direction::Direction4;
 use crate::year2022::grid::{FromChar, Grid};
+use crate::year2022::point::Point;
+
+/// Print the climb from `S` to `E` over the map while solving.
+const SHOW_CLIMB: bool = false;
 
 #[derive(Debug, Copy, Clone)]
 enum Cell {
@@ -8,6 +14,16 @@ enum Cell {
     Height(i8),
 }
 
+impl Cell {
+    fn height(self) -> i8 {
+        match self {
+            Cell::Start => 0,
+            Cell::End => 25,
+            Cell::Height(height) => height,
+        }
+    }
+}
+
 impl FromChar for Cell {
     type Error = ();
 
@@ -15,13 +31,155 @@ impl FromChar for Cell {
         match value {
             'S' => Ok(Cell::Start),
             'E' => Ok(Cell::End),
-            o if 'a' <= o && o <= 'z' => Ok(Cell::Height(o as i8 - 'a' as i8)),
+            'a'..='z' => Ok(Cell::Height(value as i8 - 'a' as i8)),
             _ => Err(()),
         }
     }
 }
 
+/// The cell one step away in `direction`, if it's still on the map.
+fn step(grid: &Grid<Cell>, index: usize, direction: Direction4) -> Option<usize> {
+    let Point { x, y } = direction.to_point_y_down::<isize>();
+    let x = (index % grid.width).checked_add_signed(x)?;
+    let y = (index / grid.width).checked_add_signed(y)?;
+
+    if x < grid.width && y < grid.height {
+        Some(grid.index(x, y))
+    } else {
+        None
+    }
+}
+
+/// Breadth first search from every source at once, following only the moves `can_move` allows.
+/// Returns the cells along the shortest path to the first cell matching `is_goal`, starting with
+/// the source it came from.
+fn shortest_path<S, M, G>(
+    grid: &Grid<Cell>,
+    sources: S,
+    can_move: M,
+    is_goal: G,
+) -> Option<Vec<usize>>
+where
+    S: IntoIterator<Item = usize>,
+    M: Fn(Cell, Cell) -> bool,
+    G: Fn(Cell) -> bool,
+{
+    let mut previous: Vec<Option<usize>> = vec![None; grid.data.len()];
+    let mut visited = vec![false; grid.data.len()];
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        visited[source] = true;
+        queue.push_back(source);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        if is_goal(grid[current]) {
+            let mut path = vec![current];
+            while let Some(prior) = previous[*path.last().unwrap()] {
+                path.push(prior);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for direction in Direction4::iter() {
+            if let Some(next) = step(grid, current, direction) {
+                if !visited[next] && can_move(grid[current], grid[next]) {
+                    visited[next] = true;
+                    previous[next] = Some(current);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn can_climb(from: Cell, to: Cell) -> bool {
+    to.height() <= from.height() + 1
+}
+
+/// Draws the path with arrows pointing to the next step, in the style of the puzzle text.
+fn render_path(grid: &Grid<Cell>, path: &[usize]) -> String {
+    let mut map = Grid::new(grid.width, grid.height, || '.');
+
+    for pair in path.windows(2) {
+        let direction = Direction4::iter()
+            .find(|&direction| step(grid, pair[0], direction) == Some(pair[1]))
+            .unwrap();
+
+        map[pair[0]] = match direction {
+            Direction4::North => '^',
+            Direction4::East => '>',
+            Direction4::South => 'v',
+            Direction4::West => '<',
+        };
+    }
+    if let Some(&end) = path.last() {
+        map[end] = 'E';
+    }
+
+    map.to_string()
+}
+
 pub fn run(input: &str) -> ChallengeResult {
     let grid: Grid<Cell> = input.parse()?;
-    Ok(Solution::from(grid.width, grid.height + grid.data.len()))
+
+    let start = grid.iter()
+        .position(|cell| matches!(cell, Cell::Start))
+        .ok_or("no start marked with S")?;
+    let end = grid.iter()
+        .position(|cell| matches!(cell, Cell::End))
+        .ok_or("no end marked with E")?;
+
+    let climb = shortest_path(&grid, [start], can_climb, |cell| matches!(cell, Cell::End));
+    if SHOW_CLIMB {
+        if let Some(path) = &climb {
+            println!("{}", render_path(&grid, path));
+        }
+    }
+
+    // walking down from the end finds whichever lowest cell is closest in a single search
+    let hike = shortest_path(
+        &grid,
+        [end],
+        |from, to| can_climb(to, from),
+        |cell| cell.height() == 0,
+    );
+
+    let steps = |path: Option<Vec<usize>>| {
+        path.map_or(Answer::Unsolved, |path| Answer::from(path.len() - 1))
+    };
+    Ok(Solution::new(steps(climb), steps(hike)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (31.into(), 29.into()));
+    }
+
+    #[test]
+    fn renders_the_climb() {
+        let grid: Grid<Cell> = EXAMPLE.parse().unwrap();
+        let path = shortest_path(&grid, [0], can_climb, |cell| matches!(cell, Cell::End)).unwrap();
+        let expected = "\
+>>vv<<<<
+..vvv<<^
+..vv>E^^
+..v>>>^^
+..>>>>>^
+";
+        let map = render_path(&grid, &path);
+        assert_eq!(map, expected);
+        assert_eq!(map.matches(['^', '>', 'v', '<']).count(), 31);
+    }
 }
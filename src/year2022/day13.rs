@@ -1,5 +1,317 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::ParseError;
+
+#[derive(Debug, Clone)]
+enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(a), Packet::Integer(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Integer(a), Packet::List(b)) => [Packet::Integer(*a)][..].cmp(b),
+            (Packet::List(a), Packet::Integer(b)) => a[..].cmp(&[Packet::Integer(*b)]),
+        }
+    }
+}
+
+/// Equal when neither is in the right order before the other, so `2`, `[2]` and `[[2]]` all match.
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Integer(value) => write!(f, "{}", value),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum PacketErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    UnclosedList,
+    NumberTooLarge,
+    TrailingInput,
+}
+
+/// A malformed packet, with the byte offset within the line where reading stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct ParsePacketError {
+    offset: usize,
+    kind: PacketErrorKind,
+}
+
+impl Display for ParsePacketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            PacketErrorKind::UnexpectedEnd => write!(f, "packet ends early"),
+            PacketErrorKind::UnexpectedChar(c) => write!(f, "unexpected '{}'", c),
+            PacketErrorKind::UnclosedList => write!(f, "list is never closed"),
+            PacketErrorKind::NumberTooLarge => write!(f, "number is too large"),
+            PacketErrorKind::TrailingInput => write!(f, "unexpected input after packet"),
+        }
+    }
+}
+
+impl Error for ParsePacketError {}
+
+struct PacketParser<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> PacketParser<'a> {
+    fn error(&self, kind: PacketErrorKind) -> ParsePacketError {
+        ParsePacketError {
+            offset: self.offset,
+            kind,
+        }
+    }
+
+    fn unexpected(&self) -> ParsePacketError {
+        match self.bytes.get(self.offset) {
+            Some(&c) => self.error(PacketErrorKind::UnexpectedChar(c as char)),
+            None => self.error(PacketErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn packet(&mut self) -> Result<Packet, ParsePacketError> {
+        match self.bytes.get(self.offset) {
+            Some(b'[') => self.list(),
+            Some(c) if c.is_ascii_digit() => self.integer(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn list(&mut self) -> Result<Packet, ParsePacketError> {
+        let open = self.offset;
+        self.offset += 1;
+
+        let mut items = Vec::new();
+        if self.bytes.get(self.offset) == Some(&b']') {
+            self.offset += 1;
+            return Ok(Packet::List(items));
+        }
+
+        loop {
+            items.push(self.packet()?);
+
+            match self.bytes.get(self.offset) {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    return Ok(Packet::List(items));
+                }
+                Some(_) => return Err(self.unexpected()),
+                // point back at the bracket that was left open
+                None => {
+                    return Err(ParsePacketError {
+                        offset: open,
+                        kind: PacketErrorKind::UnclosedList,
+                    })
+                }
+            }
+        }
+    }
+
+    fn integer(&mut self) -> Result<Packet, ParsePacketError> {
+        let start = self.offset;
+        let mut value: u32 = 0;
+
+        while let Some(&c) = self.bytes.get(self.offset).filter(|c| c.is_ascii_digit()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add((c - b'0') as u32))
+                .ok_or(ParsePacketError {
+                    offset: start,
+                    kind: PacketErrorKind::NumberTooLarge,
+                })?;
+            self.offset += 1;
+        }
+
+        Ok(Packet::Integer(value))
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParsePacketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = PacketParser {
+            bytes: s.as_bytes(),
+            offset: 0,
+        };
+
+        let packet = parser.packet()?;
+        if parser.offset < s.len() {
+            return Err(parser.error(PacketErrorKind::TrailingInput));
+        }
+
+        Ok(packet)
+    }
+}
+
+fn parse_packets(input: &str) -> Result<Vec<Packet>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            line.parse().map_err(|err: ParsePacketError| {
+                ParseError::new(i, line, err.offset..err.offset + 1, err)
+            })
+        })
+        .collect()
+}
+
+fn divider(value: u32) -> Packet {
+    Packet::List(vec![Packet::List(vec![Packet::Integer(value)])])
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let mut packets = parse_packets(input)?;
+    if packets.len() % 2 != 0 {
+        return Err("packets should come in pairs".into());
+    }
+
+    let ordered_pairs: usize = packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < pair[1])
+        .map(|(i, _)| i + 1)
+        .sum();
+
+    let dividers = [divider(2), divider(6)];
+    packets.extend(dividers.iter().cloned());
+    packets.sort_unstable();
+
+    let decoder_key: usize = dividers
+        .iter()
+        .map(|divider| packets.partition_point(|packet| packet < divider) + 1)
+        .product();
+
+    Ok(Solution::from(ordered_pairs, decoder_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    fn packet(s: &str) -> Packet {
+        s.parse().unwrap()
+    }
+
+    fn failure(s: &str) -> (usize, PacketErrorKind) {
+        let error = s.parse::<Packet>().unwrap_err();
+        (error.offset, error.kind)
+    }
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (13.into(), 140.into()));
+    }
+
+    #[test]
+    fn integer_against_list() {
+        // the integer is wrapped in a list of its own before comparing
+        assert_eq!(packet("[9]").cmp(&packet("[[8,7,6]]")), Ordering::Greater);
+        assert_eq!(packet("[[1],2]").cmp(&packet("[1,3]")), Ordering::Less);
+        assert_eq!(packet("[2,3]").cmp(&packet("[[2],4]")), Ordering::Less);
+        assert_eq!(packet("[3]").cmp(&packet("[[3]]")), Ordering::Equal);
+        assert_eq!(packet("[[3]]"), packet("[3]"));
+    }
+
+    #[test]
+    fn empty_lists() {
+        assert!(packet("[]") < packet("[[]]"));
+        assert!(packet("[[]]") < packet("[[[]]]"));
+        assert!(packet("[]") < packet("[0]"));
+        assert_eq!(packet("[]").cmp(&packet("[]")), Ordering::Equal);
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let text = "[1,[2,[3,[4,[5,6,7]]]],8,9]";
+        assert_eq!(packet(text).to_string(), text);
+        assert_eq!(packet("[[],10]").to_string(), "[[],10]");
+    }
+
+    #[test]
+    fn unbalanced_bracket_offset() {
+        assert_eq!(failure("[1,[2,3]"), (0, PacketErrorKind::UnclosedList));
+        assert_eq!(failure("[1,2]]"), (5, PacketErrorKind::TrailingInput));
+        assert_eq!(failure("[[1],[2"), (5, PacketErrorKind::UnclosedList));
+    }
+
+    #[test]
+    fn bad_token_offset() {
+        assert_eq!(failure("[1,x,3]"), (3, PacketErrorKind::UnexpectedChar('x')));
+        assert_eq!(failure("[1,,3]"), (3, PacketErrorKind::UnexpectedChar(',')));
+        assert_eq!(failure("[1,99999999999]"), (3, PacketErrorKind::NumberTooLarge));
+        assert_eq!(failure("[1,"), (3, PacketErrorKind::UnexpectedEnd));
+    }
+
+    #[test]
+    fn reports_the_line_and_column() {
+        let error = run("[1]\n[2]\n\n[3]\n[4,a]\n").unwrap_err().to_string();
+        assert!(error.starts_with("unexpected 'a' on line 5, column 4"), "{}", error);
+        assert!(error.ends_with("5 | [4,a]\n  |    ^"), "{}", error);
+    }
 }
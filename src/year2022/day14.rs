@@ -1,6 +1,8 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
 use crate::year2022::grid::Grid;
-use crate::year2022::point::Point;
+use crate::year2022::point::{polyline, LineRangeError, Point};
+use crate::year2022::rect::Rect;
 use std::fmt::{Display, Formatter, Write};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -26,53 +28,34 @@ impl Display for Cell {
     }
 }
 
-fn draw_line(map: &mut Grid<Cell>, from: Point<usize>, to: Point<usize>) {
-    if from.x == to.x {
-        let (start, end) = order(from.y, to.y);
-        for cell in map.iter_col_mut(from.x, start, end + 1) {
-            *cell = Cell::Wall;
-        }
-    } else if from.y == to.y {
-        let (start, end) = order(from.x, to.x);
-        for cell in map.iter_row_mut(from.y, start, end + 1) {
-            *cell = Cell::Wall;
-        }
-    } else {
-        panic!("can only draw a line where one axis aligns")
-    }
-}
-
-fn order(from: usize, to: usize) -> (usize, usize) {
-    if from < to {
-        (from, to)
-    } else {
-        (to, from)
-    }
-}
-
-fn parse_paths(input: &str) -> Vec<Vec<Point<usize>>> {
+fn parse_paths(input: &str) -> Result<Vec<Vec<Point<usize>>>, ParseError> {
     input
         .lines()
-        .map(|line| line.split(" -> ").map(|p| p.parse().unwrap()).collect())
+        .enumerate()
+        .map(|(i, line)| line.split(" -> ").map(|p| p.parse().at(i, line, p)).collect())
         .collect()
 }
 
-fn parse_map(paths: &Vec<Vec<Point<usize>>>) -> Grid<Cell> {
-    let max_y = paths
-        .iter()
-        .flatten()
-        .map(|point| point.y)
-        .max()
-        .unwrap_or(0);
-
-    let mut map: Grid<Cell> = Grid::new(1000, max_y + 2, Cell::default);
+fn parse_map(
+    paths: &Vec<Vec<Point<usize>>>,
+    start: Point<usize>,
+) -> Result<Grid<Cell>, LineRangeError> {
+    let rocks = Rect::from_points(paths.iter().flatten().cloned().chain([start])).unwrap();
+
+    // sand settles on the floor two rows below the lowest rock, spreading at most one column per row
+    let floor = rocks.max.y + 1;
+    let sand = Rect::new(
+        Point::new(start.x.saturating_sub(floor), start.y),
+        Point::new(start.x + floor, floor),
+    );
+    let bounds = rocks.union(&sand);
+
+    let mut map: Grid<Cell> = Grid::new(bounds.max.x + 2, bounds.max.y + 1, Cell::default);
     for path in paths.iter() {
-        for (&from, &to) in path.iter().zip(&path[1..]) {
-            draw_line(&mut map, from, to);
-        }
+        map.draw(polyline(path)?, Cell::Wall);
     }
 
-    map
+    Ok(map)
 }
 
 fn drop_sand(map: &mut Grid<Cell>, start: Point<usize>) -> Point<usize> {
@@ -97,10 +80,9 @@ fn drop_sand(map: &mut Grid<Cell>, start: Point<usize>) -> Point<usize> {
 }
 
 pub fn run(input: &str) -> ChallengeResult {
-    let paths = parse_paths(input);
-    let mut map = parse_map(&paths);
-
+    let paths = parse_paths(input)?;
     let start = Point::new(500, 0);
+    let mut map = parse_map(&paths, start)?;
 
     let mut count = 0;
     while drop_sand(&mut map, start).y < map.height - 1 {
@@ -116,3 +98,24 @@ pub fn run(input: &str) -> ChallengeResult {
 
     Ok(Solution::from(part1, count + 2))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (24.into(), 93.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_paths("498,4 -> 498,6\n503,4 -> 502;4 -> 502,9\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 10));
+        let snippet = "2 | 503,4 -> 502;4 -> 502,9\n  |          ^^^^^";
+        assert!(error.to_string().ends_with(snippet), "{}", error);
+    }
+}
@@ -1,5 +1,150 @@
-use crate::challenge_result::{ChallengeResult, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::challenge_result::{Answer, ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::interval::{Interval, IntervalSet};
+use crate::year2022::point::Point;
+
+/// Which row to count and how far to look for the distress beacon.
+#[derive(Debug, Copy, Clone)]
+struct Search {
+    row: i64,
+    bound: i64,
+}
+
+const REAL: Search = Search { row: 2_000_000, bound: 4_000_000 };
+
+#[derive(Debug, Copy, Clone)]
+struct Sensor {
+    position: Point<i64>,
+    beacon: Point<i64>,
+    radius: i64,
+}
+
+impl Sensor {
+    /// The cells on `row` that are no further away than the closest beacon.
+    fn coverage(&self, row: i64) -> Option<Interval<i64>> {
+        let spread = self.radius - (self.position.y - row).abs();
+        if spread < 0 {
+            None
+        } else {
+            Some(Interval::new(self.position.x - spread, self.position.x + spread))
+        }
+    }
+}
+
+fn parse_sensors(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$"
+        )
+        .unwrap();
+    }
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let cap = RE
+                .captures(line)
+                .ok_or("expected \"Sensor at x=.., y=..: closest beacon is at x=.., y=..\"")
+                .on_line(i, line)?;
+
+            let mut values = [0i64; 4];
+            for (value, group) in values.iter_mut().zip(1..) {
+                let field = &line[cap.get(group).unwrap().range()];
+                *value = field.parse().at(i, line, field)?;
+            }
+
+            let position = Point::new(values[0], values[1]);
+            let beacon = Point::new(values[2], values[3]);
+            Ok(Sensor {
+                position,
+                beacon,
+                radius: position.manhattan_distance(beacon),
+            })
+        })
+        .collect()
+}
+
+fn coverage(sensors: &[Sensor], row: i64) -> IntervalSet<i64> {
+    sensors.iter().filter_map(|sensor| sensor.coverage(row)).collect()
+}
+
+/// Positions on `row` that can't hold an undiscovered beacon, or `None` if there are too many
+/// to count in an `i64`.
+fn excluded_on_row(sensors: &[Sensor], row: i64) -> Option<i64> {
+    let covered = coverage(sensors, row);
+
+    let mut beacons: Vec<i64> = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.y == row && covered.contains(sensor.beacon.x))
+        .map(|sensor| sensor.beacon.x)
+        .collect();
+    beacons.sort_unstable();
+    beacons.dedup();
+
+    Some(covered.covered()? - beacons.len() as i64)
+}
+
+/// The first position with both coordinates between zero and `bound` that no sensor covers.
+fn find_beacon(sensors: &[Sensor], bound: i64) -> Option<Point<i64>> {
+    (0..=bound).find_map(|y| {
+        coverage(sensors, y)
+            .gaps(Interval::new(0, bound))
+            .first()
+            .map(|gap| Point::new(gap.start, y))
+    })
+}
+
+fn tuning_frequency(beacon: Point<i64>) -> i64 {
+    beacon.x * 4_000_000 + beacon.y
+}
+
+fn solve(sensors: &[Sensor], search: Search) -> Solution {
+    let excluded = excluded_on_row(sensors, search.row).map_or(Answer::Unsolved, Answer::from);
+    let frequency = find_beacon(sensors, search.bound)
+        .map_or(Answer::Unsolved, |beacon| Answer::from(tuning_frequency(beacon)));
+
+    Solution::new(excluded, frequency)
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let sensors = parse_sensors(input)?;
+    Ok(solve(&sensors, REAL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The puzzle text asks about a smaller area for its example.
+    const EXAMPLE: Search = Search { row: 10, bound: 20 };
+
+    const INPUT: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn example() {
+        let sensors = parse_sensors(INPUT).unwrap();
+        assert_eq!(excluded_on_row(&sensors, EXAMPLE.row), Some(26));
+        assert_eq!(find_beacon(&sensors, EXAMPLE.bound), Some(Point::new(14, 11)));
+
+        let solution = solve(&sensors, EXAMPLE);
+        assert_eq!((solution.part1, solution.part2), (26.into(), 56_000_011.into()));
+    }
 }
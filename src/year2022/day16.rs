@@ -1,5 +1,238 @@
+use hashbrown::hash_map::Entry;
+use hashbrown::HashMap;
+use lazy_static::lazy_static;
+use regex::Regex;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
+
+const START: &str = "AA";
+
+#[derive(Debug, Clone)]
+struct Valve {
+    flow: u32,
+    tunnels: Vec<usize>,
+}
+
+/// Valves indexed in the order they're listed, with `names` mapping back to the input.
+#[derive(Debug, Clone)]
+struct Network<'a> {
+    names: HashMap<&'a str, usize>,
+    valves: Vec<Valve>,
+}
+
+fn parse_network(input: &str) -> Result<Network<'_>, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"^Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (\w+(?:, \w+)*)$"
+        )
+        .unwrap();
+    }
+
+    let mut rows = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let cap = RE
+            .captures(line)
+            .ok_or("expected \"Valve XX has flow rate=N; tunnels lead to valves YY, ZZ\"")
+            .on_line(i, line)?;
+
+        let [name, flow, tunnels] = [1, 2, 3].map(|group| cap.get(group).unwrap().as_str());
+        let flow: u32 = flow.parse().at(i, line, flow)?;
+        rows.push((i, line, name, flow, tunnels));
+    }
+
+    let mut names = HashMap::new();
+    for &(i, line, name, ..) in rows.iter() {
+        if names.insert(name, names.len()).is_some() {
+            return Err("valve is listed twice").at(i, line, name);
+        }
+    }
+
+    let valves = rows
+        .iter()
+        .map(|&(i, line, _, flow, tunnels)| {
+            let tunnels = tunnels
+                .split(", ")
+                .map(|tunnel| {
+                    names.get(tunnel).copied().ok_or("no such valve").at(i, line, tunnel)
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Valve { flow, tunnels })
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok(Network { names, valves })
+}
+
+/// Minutes needed to walk between every pair of valves, by Floyd-Warshall.
+fn distances(valves: &[Valve]) -> Vec<Vec<u32>> {
+    let unreachable = u32::MAX / 2;
+    let mut distances = vec![vec![unreachable; valves.len()]; valves.len()];
+
+    for (from, valve) in valves.iter().enumerate() {
+        distances[from][from] = 0;
+        for &to in valve.tunnels.iter() {
+            distances[from][to] = 1;
+        }
+    }
+
+    for via in 0..valves.len() {
+        for from in 0..valves.len() {
+            for to in 0..valves.len() {
+                let through = distances[from][via] + distances[via][to];
+                if through < distances[from][to] {
+                    distances[from][to] = through;
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+/// State carried through `Condensed::explore`.
+struct Search {
+    /// The most pressure released by each set of opened valves.
+    best: Vec<u32>,
+    /// The most pressure released on reaching each valve with the time left and valves opened.
+    seen: HashMap<(usize, u32, usize), u32>,
+}
+
+/// The network reduced to the starting valve and the valves worth opening.
+struct Condensed {
+    start: usize,
+    flows: Vec<u32>,
+    /// `distances[a][b]` between useful valves, `distances[start]` from the starting valve.
+    distances: Vec<Vec<u32>>,
+}
+
+impl Condensed {
+    fn new(network: &Network) -> Result<Condensed, &'static str> {
+        let start = *network.names.get(START).ok_or("no valve AA to start from")?;
+        let all_distances = distances(&network.valves);
+
+        let useful: Vec<usize> = (0..network.valves.len())
+            .filter(|&valve| network.valves[valve].flow > 0)
+            .collect();
+        if useful.len() > 20 {
+            return Err("too many valves with a flow rate to track in a bitmask");
+        }
+
+        let mut distances: Vec<Vec<u32>> = useful
+            .iter()
+            .map(|&from| useful.iter().map(|&to| all_distances[from][to]).collect())
+            .collect();
+        distances.push(useful.iter().map(|&to| all_distances[start][to]).collect());
+
+        Ok(Condensed {
+            start: useful.len(),
+            flows: useful.iter().map(|&valve| network.valves[valve].flow).collect(),
+            distances,
+        })
+    }
+
+    /// The most pressure released by opening exactly the valves in each bitmask within `minutes`,
+    /// visiting them in the best order. Sets that can't all be opened in time are left at zero.
+    fn best_by_opened(&self, minutes: u32) -> Vec<u32> {
+        let mut search = Search {
+            best: vec![0; 1 << self.flows.len()],
+            seen: HashMap::new(),
+        };
+        self.explore(self.start, minutes, 0, 0, &mut search);
+        search.best
+    }
+
+    fn explore(
+        &self,
+        at: usize,
+        remaining: u32,
+        opened: usize,
+        released: u32,
+        search: &mut Search,
+    ) {
+        // the same valve, time and open set always lead on the same way, so a second visit only
+        // matters if it has already released more
+        match search.seen.entry((at, remaining, opened)) {
+            Entry::Occupied(entry) if *entry.get() >= released => return,
+            Entry::Occupied(mut entry) => {
+                entry.insert(released);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(released);
+            }
+        }
+        search.best[opened] = search.best[opened].max(released);
+
+        for (next, &flow) in self.flows.iter().enumerate() {
+            let cost = self.distances[at][next] + 1;
+            if opened & (1 << next) != 0 || cost >= remaining {
+                continue;
+            }
+
+            let remaining = remaining - cost;
+            let released = released + flow * remaining;
+            self.explore(next, remaining, opened | (1 << next), released, search);
+        }
+    }
+}
+
+fn alone(condensed: &Condensed) -> u32 {
+    condensed.best_by_opened(30).into_iter().max().unwrap_or(0)
+}
+
+/// Splits the valves between you and the elephant, each taking the best route through their share.
+fn with_elephant(condensed: &Condensed) -> u32 {
+    let mut best = condensed.best_by_opened(26);
+
+    // let every set also stand for its best subset, so a share doesn't have to be opened in full
+    for bit in 0..condensed.flows.len() {
+        for opened in 0..best.len() {
+            if opened & (1 << bit) != 0 {
+                best[opened] = best[opened].max(best[opened ^ (1 << bit)]);
+            }
+        }
+    }
+
+    let all = best.len() - 1;
+    (0..best.len())
+        .map(|mine| best[mine] + best[all ^ mine])
+        .max()
+        .unwrap_or(0)
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let network = parse_network(input)?;
+    let condensed = Condensed::new(&network)?;
+
+    Ok(Solution::from(alone(&condensed), with_elephant(&condensed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (1651.into(), 1707.into()));
+    }
+
+    #[test]
+    fn reports_missing_start() {
+        let input = EXAMPLE.replace("AA", "ZZ");
+        let error = run(&input).unwrap_err().to_string();
+        assert_eq!(error, "no valve AA to start from");
+    }
 }
@@ -1,5 +1,193 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::cycle::CycleDetector;
+
+const WIDTH: usize = 7;
+
+/// Rocks as rows from the bottom up, one bit per column with the leftmost column in bit 6,
+/// already placed two columns in from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Jet {
+    Left,
+    Right,
+}
+
+fn parse_jets(input: &str) -> Result<Vec<Jet>, ParseError> {
+    let line = input.trim_end();
+
+    let jets = line
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err("expected '<' or '>'").at(0, line, &line[i..i + c.len_utf8()]),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if jets.is_empty() {
+        return Err("no jet pattern").on_line(0, line);
+    }
+    Ok(jets)
+}
+
+/// Everything that decides how the next rock lands: which rock and jet come next, and how far
+/// down each column's topmost rock is.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct State {
+    rock: usize,
+    jet: usize,
+    profile: [usize; WIDTH],
+}
+
+struct Chamber {
+    rows: Vec<u8>,
+    jets: Vec<Jet>,
+    next_jet: usize,
+    next_rock: usize,
+}
+
+impl Chamber {
+    fn new(jets: Vec<Jet>) -> Chamber {
+        Chamber {
+            rows: Vec::new(),
+            jets,
+            next_jet: 0,
+            next_rock: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .zip(bottom..)
+            .any(|(&row, y)| matches!(self.rows.get(y), Some(&filled) if filled & row != 0))
+    }
+
+    fn push(&self, rock: &[u8], bottom: usize, jet: Jet) -> Option<Vec<u8>> {
+        let shifted: Vec<u8> = match jet {
+            Jet::Left if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+                rock.iter().map(|row| row << 1).collect()
+            }
+            Jet::Right if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+                rock.iter().map(|row| row >> 1).collect()
+            }
+            _ => return None,
+        };
+
+        if self.collides(&shifted, bottom) {
+            None
+        } else {
+            Some(shifted)
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.next_rock].to_vec();
+        self.next_rock = (self.next_rock + 1) % ROCKS.len();
+
+        let mut bottom = self.height() + 3;
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+
+            if let Some(pushed) = self.push(&rock, bottom, jet) {
+                rock = pushed;
+            }
+
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        for (row, y) in rock.into_iter().zip(bottom..) {
+            if y == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y] |= row;
+        }
+    }
+
+    fn state(&self) -> State {
+        let mut profile = [self.height(); WIDTH];
+        for (column, depth) in profile.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+            if let Some(top) = self.rows.iter().rposition(|row| row & bit != 0) {
+                *depth = self.height() - 1 - top;
+            }
+        }
+
+        State {
+            rock: self.next_rock,
+            jet: self.next_jet,
+            profile,
+        }
+    }
+}
+
+/// How tall the tower stands after `rocks` have fallen, simulating only until the way rocks
+/// land starts repeating.
+fn height_after(jets: &[Jet], rocks: usize) -> u64 {
+    let mut chamber = Chamber::new(jets.to_vec());
+    let mut detector = CycleDetector::new();
+
+    // heights[n] is the height of the tower after n rocks
+    let mut heights = vec![0];
+    let cycle = loop {
+        if let Some(cycle) = detector.observe(chamber.state()) {
+            break cycle;
+        }
+
+        chamber.drop_rock();
+        heights.push(chamber.height());
+    };
+
+    cycle.extrapolate(rocks, |n| heights[n] as u64)
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let jets = parse_jets(input)?;
+    Ok(Solution::from(height_after(&jets, 2022), height_after(&jets, 1_000_000_000_000)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!(
+            (solution.part1, solution.part2),
+            (3068.into(), 1_514_285_714_288u64.into())
+        );
+    }
+
+    #[test]
+    fn extrapolation_matches_simulation() {
+        let jets = parse_jets(EXAMPLE).unwrap();
+
+        let mut simulated = Chamber::new(jets.clone());
+        for rocks in 1..=3000 {
+            simulated.drop_rock();
+            let height = simulated.height() as u64;
+            assert_eq!(height_after(&jets, rocks), height, "after {} rocks", rocks);
+        }
+    }
 }
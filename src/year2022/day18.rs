@@ -1,5 +1,201 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::parse_lines;
+
+const FACES: [[i32; 3]; 6] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Cube([i32; 3]);
+
+#[derive(Debug, Copy, Clone)]
+struct ParseCubeError;
+
+impl Display for ParseCubeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a cube as \"x,y,z\"")
+    }
+}
+
+impl Error for ParseCubeError {}
+
+impl FromStr for Cube {
+    type Err = ParseCubeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coordinates = s.split(',').map(|value| value.trim().parse::<i32>());
+
+        let mut cube = [0; 3];
+        for coordinate in cube.iter_mut() {
+            *coordinate = coordinates
+                .next()
+                .and_then(|value| value.ok())
+                .ok_or(ParseCubeError)?;
+        }
+
+        if coordinates.next().is_some() {
+            return Err(ParseCubeError);
+        }
+        Ok(Cube(cube))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Voxel {
+    Air,
+    Lava,
+    Steam,
+}
+
+/// The droplet's bounding box grown by one in every direction, so steam can reach all the way
+/// around it.
+struct Space {
+    min: [i32; 3],
+    size: [usize; 3],
+    voxels: Vec<Voxel>,
+}
+
+impl Space {
+    fn new(cubes: &[Cube]) -> Space {
+        let mut min = [i32::MAX; 3];
+        let mut max = [i32::MIN; 3];
+        for Cube(position) in cubes {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis] - 1);
+                max[axis] = max[axis].max(position[axis] + 1);
+            }
+        }
+
+        let size = [0, 1, 2].map(|axis| (max[axis] - min[axis] + 1) as usize);
+        let mut space = Space {
+            min,
+            size,
+            voxels: vec![Voxel::Air; size.iter().product()],
+        };
+
+        for &Cube(position) in cubes {
+            let index = space.index(position).unwrap();
+            space.voxels[index] = Voxel::Lava;
+        }
+        space
+    }
+
+    fn index(&self, position: [i32; 3]) -> Option<usize> {
+        let mut index = 0;
+        for axis in (0..3).rev() {
+            let offset = usize::try_from(position[axis] - self.min[axis]).ok()?;
+            if offset >= self.size[axis] {
+                return None;
+            }
+            index = index * self.size[axis] + offset;
+        }
+        Some(index)
+    }
+
+    fn neighbours(position: [i32; 3]) -> impl Iterator<Item = [i32; 3]> {
+        FACES.iter().map(move |face| [0, 1, 2].map(|axis| position[axis] + face[axis]))
+    }
+
+    /// Faces of lava that touch anything other than lava, including trapped air.
+    fn surface_area(&self, cubes: &[Cube]) -> usize {
+        cubes
+            .iter()
+            .flat_map(|&Cube(position)| Space::neighbours(position))
+            .filter(|&neighbour| {
+                !self.index(neighbour).is_some_and(|index| self.voxels[index] == Voxel::Lava)
+            })
+            .count()
+    }
+
+    /// Fills the air outside the droplet with steam, counting each lava face the steam reaches.
+    fn exterior_surface_area(&mut self) -> usize {
+        let mut faces = 0;
+        let mut stack = vec![self.min];
+        let start = self.index(self.min).unwrap();
+        self.voxels[start] = Voxel::Steam;
+
+        while let Some(position) = stack.pop() {
+            for neighbour in Space::neighbours(position) {
+                let Some(index) = self.index(neighbour) else {
+                    continue;
+                };
+
+                match self.voxels[index] {
+                    Voxel::Lava => faces += 1,
+                    Voxel::Air => {
+                        self.voxels[index] = Voxel::Steam;
+                        stack.push(neighbour);
+                    }
+                    Voxel::Steam => {}
+                }
+            }
+        }
+
+        faces
+    }
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let cubes: Vec<Cube> = parse_lines(input)?;
+    if cubes.is_empty() {
+        return Err("expected at least one cube".into());
+    }
+
+    let mut space = Space::new(&cubes);
+    let surface = space.surface_area(&cubes);
+    let exterior = space.exterior_surface_area();
+
+    Ok(Solution::from(surface, exterior))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (64.into(), 58.into()));
+    }
+
+    #[test]
+    fn enclosed_air_pocket() {
+        // a hollow 3x3x3 cube with one cell of trapped air in the middle
+        let shell: String = (0..27)
+            .filter(|&i| i != 13)
+            .map(|i| format!("{},{},{}\n", i % 3, i / 3 % 3, i / 9))
+            .collect();
+
+        let solution = run(&shell).unwrap();
+        assert_eq!((solution.part1, solution.part2), (60.into(), 54.into()));
+    }
+
+    #[test]
+    fn reports_empty_input() {
+        assert_eq!(run("").unwrap_err().to_string(), "expected at least one cube");
+    }
 }
@@ -1,5 +1,205 @@
+use lazy_static::lazy_static;
+use regex::Regex;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{check_skipped, Context, ParseError};
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+/// Amounts of ore, clay, obsidian and geodes, indexed by the constants above.
+type Resources = [u32; 4];
+
+#[derive(Debug, Clone)]
+struct Blueprint {
+    id: u32,
+    /// What each kind of robot costs to build.
+    costs: [Resources; 4],
+    /// The most of each resource any robot needs, as there's no use collecting more per minute
+    /// than can be spent.
+    most_needed: Resources,
+}
+
+impl Blueprint {
+    fn new(id: u32, costs: [Resources; 4]) -> Blueprint {
+        let mut most_needed = [0; 4];
+        for cost in costs.iter() {
+            for (needed, &amount) in most_needed.iter_mut().zip(cost) {
+                *needed = (*needed).max(amount);
+            }
+        }
+        // geodes are never spent, more geode robots are always better
+        most_needed[GEODE] = u32::MAX;
+
+        Blueprint {
+            id,
+            costs,
+            most_needed,
+        }
+    }
+}
+
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"(?x)
+                Blueprint\ (\d+):\s+
+                    Each\ ore\ robot\ costs\ (\d+)\ ore\.\s+
+                    Each\ clay\ robot\ costs\ (\d+)\ ore\.\s+
+                    Each\ obsidian\ robot\ costs\ (\d+)\ ore\ and\ (\d+)\ clay\.\s+
+                    Each\ geode\ robot\ costs\ (\d+)\ ore\ and\ (\d+)\ obsidian\.
+            "
+        )
+        .unwrap();
+    }
+
+    let mut blueprints = Vec::new();
+    let mut parsed_up_to = 0;
+    for cap in RE.captures_iter(input) {
+        let whole = cap.get(0).unwrap();
+        check_skipped(input, parsed_up_to..whole.start(), "expected a blueprint")?;
+        parsed_up_to = whole.end();
+
+        let mut values = [0u32; 7];
+        for (value, group) in values.iter_mut().zip(1..) {
+            let field = cap.get(group).unwrap();
+            *value = field.as_str().parse().at_offset(input, field.range())?;
+        }
+
+        let mut costs = [[0; 4]; 4];
+        costs[ORE][ORE] = values[1];
+        costs[CLAY][ORE] = values[2];
+        costs[OBSIDIAN][ORE] = values[3];
+        costs[OBSIDIAN][CLAY] = values[4];
+        costs[GEODE][ORE] = values[5];
+        costs[GEODE][OBSIDIAN] = values[6];
+
+        blueprints.push(Blueprint::new(values[0], costs));
+    }
+    check_skipped(input, parsed_up_to..input.len(), "expected a blueprint")?;
+
+    Ok(blueprints)
+}
+
+/// Complains about anything but whitespace between blueprints, which the regex would otherwise
+/// silently step over.
+#[derive(Debug, Copy, Clone)]
+struct State {
+    remaining: u32,
+    robots: Resources,
+    resources: Resources,
+}
+
+impl State {
+    /// Geodes at the end if no more robots were built.
+    fn idle_geodes(&self) -> u32 {
+        self.resources[GEODE] + self.robots[GEODE] * self.remaining
+    }
+
+    /// Geodes at the end if a geode robot could be built every remaining minute.
+    fn optimistic_geodes(&self) -> u32 {
+        self.idle_geodes() + self.remaining * self.remaining.saturating_sub(1) / 2
+    }
+
+    /// Waits until the robot can be afforded, then builds it. `None` when it can't be built in
+    /// time to collect anything.
+    fn build(&self, robot: usize, cost: &Resources) -> Option<State> {
+        let mut wait = 0;
+        for ((&needed, &stored), &robots) in cost.iter().zip(&self.resources).zip(&self.robots) {
+            let missing = needed.saturating_sub(stored);
+            if missing > 0 {
+                if robots == 0 {
+                    return None;
+                }
+                wait = wait.max(missing.div_ceil(robots));
+            }
+        }
+
+        let elapsed = wait + 1;
+        if elapsed >= self.remaining {
+            return None;
+        }
+
+        let mut next = *self;
+        next.remaining -= elapsed;
+        for ((stored, &robots), &spent) in next.resources.iter_mut().zip(&self.robots).zip(cost) {
+            *stored = *stored + robots * elapsed - spent;
+        }
+        next.robots[robot] += 1;
+        Some(next)
+    }
+}
+
+/// Depth first search over which robot to build next, skipping the minutes spent waiting for it.
+fn search(blueprint: &Blueprint, state: State, best: &mut u32) {
+    *best = (*best).max(state.idle_geodes());
+    if state.optimistic_geodes() <= *best {
+        return;
+    }
+
+    // trying geode robots first finds good answers early, which makes the bound prune more
+    for robot in (0..4).rev() {
+        if state.robots[robot] >= blueprint.most_needed[robot] {
+            continue;
+        }
+
+        if let Some(next) = state.build(robot, &blueprint.costs[robot]) {
+            search(blueprint, next, best);
+        }
+    }
+}
+
+/// Most geodes the blueprint can open in the time given. Each blueprint is independent of the
+/// others.
+fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let start = State {
+        remaining: minutes,
+        robots: [1, 0, 0, 0],
+        resources: [0; 4],
+    };
+
+    let mut best = 0;
+    search(blueprint, start, &mut best);
+    best
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let blueprints = parse_blueprints(input)?;
+
+    let quality_levels: u32 = blueprints
+        .iter()
+        .map(|blueprint| blueprint.id * max_geodes(blueprint, 24))
+        .sum();
+
+    let first_three: u32 = blueprints
+        .iter()
+        .take(3)
+        .map(|blueprint| max_geodes(blueprint, 32))
+        .product();
+
+    Ok(Solution::from(quality_levels, first_three))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn example() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        let geodes = |minutes| -> Vec<u32> {
+            blueprints.iter().map(|blueprint| max_geodes(blueprint, minutes)).collect()
+        };
+        assert_eq!(geodes(24), [9, 12]);
+        assert_eq!(geodes(32), [56, 62]);
+
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (33.into(), 3472.into()));
+    }
 }
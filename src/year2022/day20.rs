@@ -1,5 +1,88 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::parse_lines;
+
+const DECRYPTION_KEY: i64 = 811589153;
+const GROVE_OFFSETS: [usize; 3] = [1000, 2000, 3000];
+
+/// Moves every number, in its original order, forwards or backwards around the circle by its
+/// value, `rounds` times over. Returns the numbers in their final order.
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    // positions hold indices into `numbers` so duplicate values stay distinguishable
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    let others = numbers.len() as i64 - 1;
+
+    if others > 0 {
+        for _ in 0..rounds {
+            for (original, &value) in numbers.iter().enumerate() {
+                let position = order.iter().position(|&index| index == original).unwrap();
+                order.remove(position);
+
+                // with the number lifted out there are only `others` gaps to drop it back into
+                let destination = (position as i64 + value).rem_euclid(others) as usize;
+                order.insert(destination, original);
+            }
+        }
+    }
+
+    order.into_iter().map(|index| numbers[index]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> Result<i64, &'static str> {
+    let zero = mixed
+        .iter()
+        .position(|&value| value == 0)
+        .ok_or("the file has no 0 to count from")?;
+
+    Ok(GROVE_OFFSETS
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum())
+}
+
+fn decrypt(numbers: &[i64], key: i64, rounds: usize) -> Result<i64, &'static str> {
+    let numbers: Vec<i64> = numbers
+        .iter()
+        .map(|value| value.checked_mul(key))
+        .collect::<Option<_>>()
+        .ok_or("number is too large to apply the decryption key")?;
+
+    grove_coordinates(&mix(&numbers, rounds))
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let numbers: Vec<i64> = parse_lines(input)?;
+
+    let part1 = decrypt(&numbers, 1, 1)?;
+    let part2 = decrypt(&numbers, DECRYPTION_KEY, 10)?;
+
+    Ok(Solution::from(part1, part2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (3.into(), 1623178306.into()));
+    }
+
+    /// The circle read from its zero, since where the list starts doesn't matter.
+    fn from_zero(mut numbers: Vec<i64>) -> Vec<i64> {
+        let zero = numbers.iter().position(|&value| value == 0).unwrap();
+        numbers.rotate_left(zero);
+        numbers
+    }
+
+    #[test]
+    fn mixes_by_original_index() {
+        assert_eq!(from_zero(mix(&[1, 2, -3, 3, -2, 0, 4], 1)), [0, 3, -2, 1, 2, -3, 4]);
+
+        // each 1 moves once; finding them by value would move the first one twice instead,
+        // leaving 0, 3, -1, 1, 1
+        assert_eq!(from_zero(mix(&[1, -1, 1, 0, 3], 1)), [0, 1, 3, -1, 1]);
+    }
 }
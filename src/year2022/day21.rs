@@ -1,5 +1,306 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use hashbrown::HashMap;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Job {
+    Number(i64),
+    Operation(usize, Operator, usize),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum JobError {
+    Overflow,
+    DivisionByZero,
+    InexactDivision,
+    Loop,
+    UnknownOnBothSides,
+    UnknownMissing,
+}
+
+impl Display for JobError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobError::Overflow => write!(f, "a monkey's number is too large"),
+            JobError::DivisionByZero => write!(f, "a monkey divides by zero"),
+            JobError::InexactDivision => write!(f, "a monkey's division leaves a remainder"),
+            JobError::Loop => write!(f, "monkeys wait on each other in a loop"),
+            JobError::UnknownOnBothSides => {
+                write!(f, "{} appears on both sides of {}'s comparison", HUMAN, ROOT)
+            }
+            JobError::UnknownMissing => write!(f, "{} doesn't affect {}", HUMAN, ROOT),
+        }
+    }
+}
+
+impl Error for JobError {}
+
+fn apply(left: i64, operator: Operator, right: i64) -> Result<i64, JobError> {
+    match operator {
+        Operator::Add => left.checked_add(right).ok_or(JobError::Overflow),
+        Operator::Sub => left.checked_sub(right).ok_or(JobError::Overflow),
+        Operator::Mul => left.checked_mul(right).ok_or(JobError::Overflow),
+        Operator::Div => exact_div(left, right),
+    }
+}
+
+fn exact_div(left: i64, right: i64) -> Result<i64, JobError> {
+    if right == 0 {
+        Err(JobError::DivisionByZero)
+    } else if left % right != 0 {
+        Err(JobError::InexactDivision)
+    } else {
+        left.checked_div(right).ok_or(JobError::Overflow)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Visit {
+    Unvisited,
+    InProgress,
+    Done(i64),
+}
+
+/// Every monkey's job, with names interned to indices so jobs can share monkeys.
+struct Monkeys<'a> {
+    names: HashMap<&'a str, usize>,
+    jobs: Vec<Job>,
+}
+
+fn parse_monkeys(input: &str) -> Result<Monkeys<'_>, ParseError> {
+    let mut rows = Vec::new();
+    let mut names = HashMap::new();
+    for (i, line) in input.lines().enumerate() {
+        let (name, job) = line
+            .split_once(": ")
+            .ok_or("expected \"name: job\"")
+            .on_line(i, line)?;
+        if names.insert(name, names.len()).is_some() {
+            return Err("monkey is listed twice").at(i, line, name);
+        }
+        rows.push((i, line, job));
+    }
+
+    let monkey = |i: usize, line: &str, name: &str| {
+        names.get(name).copied().ok_or("no such monkey").at(i, line, name)
+    };
+
+    let jobs = rows
+        .into_iter()
+        .map(|(i, line, job)| {
+            let parts: Vec<&str> = job.split(' ').collect();
+            match parts[..] {
+                [number] => Ok(Job::Number(number.parse().at(i, line, number)?)),
+                [left, operator, right] => {
+                    let operator = match operator {
+                        "+" => Operator::Add,
+                        "-" => Operator::Sub,
+                        "*" => Operator::Mul,
+                        "/" => Operator::Div,
+                        _ => return Err("expected one of + - * /").at(i, line, operator),
+                    };
+                    Ok(Job::Operation(monkey(i, line, left)?, operator, monkey(i, line, right)?))
+                }
+                _ => Err("expected a number or \"name op name\"").at(i, line, job),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Monkeys { names, jobs })
+}
+
+impl Monkeys<'_> {
+    fn find(&self, name: &str) -> Result<usize, String> {
+        self.names.get(name).copied().ok_or_else(|| format!("no monkey named {}", name))
+    }
+}
+
+/// Works out numbers on demand, remembering each so shared monkeys are only evaluated once.
+struct Evaluator<'a> {
+    jobs: &'a [Job],
+    visits: Vec<Visit>,
+    /// Whether each monkey's number depends on the unknown's, filled in while solving.
+    dependent: Vec<Option<bool>>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(jobs: &'a [Job]) -> Evaluator<'a> {
+        Evaluator {
+            jobs,
+            visits: vec![Visit::Unvisited; jobs.len()],
+            dependent: vec![None; jobs.len()],
+        }
+    }
+
+    fn evaluate(&mut self, monkey: usize) -> Result<i64, JobError> {
+        match self.visits[monkey] {
+            Visit::Done(value) => return Ok(value),
+            Visit::InProgress => return Err(JobError::Loop),
+            Visit::Unvisited => {}
+        }
+
+        self.visits[monkey] = Visit::InProgress;
+        let value = match self.jobs[monkey] {
+            Job::Number(value) => value,
+            Job::Operation(left, operator, right) => {
+                apply(self.evaluate(left)?, operator, self.evaluate(right)?)?
+            }
+        };
+
+        self.visits[monkey] = Visit::Done(value);
+        Ok(value)
+    }
+
+    /// Whether `unknown`'s number feeds into `monkey`'s.
+    fn depends_on(&mut self, monkey: usize, unknown: usize) -> bool {
+        if let Some(dependent) = self.dependent[monkey] {
+            return dependent;
+        }
+
+        let dependent = monkey == unknown
+            || match self.jobs[monkey] {
+                Job::Number(_) => false,
+                Job::Operation(left, _, right) => {
+                    self.depends_on(left, unknown) || self.depends_on(right, unknown)
+                }
+            };
+
+        self.dependent[monkey] = Some(dependent);
+        dependent
+    }
+
+    /// The number `unknown` must yell for `root`'s two sides to match, found by undoing each
+    /// operation on the way down from `root` to `unknown`.
+    fn solve(&mut self, root: usize, unknown: usize) -> Result<i64, JobError> {
+        let Job::Operation(left, _, right) = self.jobs[root] else {
+            return Err(JobError::UnknownMissing);
+        };
+
+        self.dependent.fill(None);
+        let (mut monkey, mut target) = self.split(left, right, unknown)?;
+        while monkey != unknown {
+            let Job::Operation(left, operator, right) = self.jobs[monkey] else {
+                unreachable!("only operations depend on another monkey")
+            };
+
+            let unknown_on_left = self.depends_on(left, unknown);
+            let (next, known) = self.split(left, right, unknown)?;
+
+            target = match (operator, unknown_on_left) {
+                (Operator::Add, _) => target.checked_sub(known).ok_or(JobError::Overflow)?,
+                (Operator::Mul, _) => exact_div(target, known)?,
+                // x - known = target
+                (Operator::Sub, true) => target.checked_add(known).ok_or(JobError::Overflow)?,
+                // known - x = target
+                (Operator::Sub, false) => known.checked_sub(target).ok_or(JobError::Overflow)?,
+                // x / known = target
+                (Operator::Div, true) => target.checked_mul(known).ok_or(JobError::Overflow)?,
+                // known / x = target
+                (Operator::Div, false) => exact_div(known, target)?,
+            };
+            monkey = next;
+        }
+
+        Ok(target)
+    }
+
+    /// Picks out the side that depends on `unknown` and evaluates the other.
+    fn split(
+        &mut self,
+        left: usize,
+        right: usize,
+        unknown: usize,
+    ) -> Result<(usize, i64), JobError> {
+        match (self.depends_on(left, unknown), self.depends_on(right, unknown)) {
+            (true, true) => Err(JobError::UnknownOnBothSides),
+            (false, false) => Err(JobError::UnknownMissing),
+            (true, false) => Ok((left, self.evaluate(right)?)),
+            (false, true) => Ok((right, self.evaluate(left)?)),
+        }
+    }
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let monkeys = parse_monkeys(input)?;
+    let root = monkeys.find(ROOT)?;
+    let human = monkeys.find(HUMAN)?;
+
+    let mut evaluator = Evaluator::new(&monkeys.jobs);
+    let yelled = evaluator.evaluate(root)?;
+    let needed = evaluator.solve(root, human)?;
+
+    Ok(Solution::from(yelled, needed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    fn error(input: &str) -> String {
+        run(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (152.into(), 301.into()));
+    }
+
+    #[test]
+    fn unknown_on_both_sides() {
+        let input = "root: left + right\nleft: humn * two\nright: humn + two\nhumn: 5\ntwo: 2\n";
+        assert_eq!(error(input), "humn appears on both sides of root's comparison");
+
+        // sharing the unknown further down is just as unsolvable by undoing one step at a time
+        let input = "root: sum + nine\nsum: humn + square\nsquare: humn * humn\nhumn: 1\nnine: 9";
+        assert_eq!(error(input), "humn appears on both sides of root's comparison");
+    }
+
+    #[test]
+    fn inexact_division() {
+        // humn * 2 would have to be 7
+        let input = "root: double + seven\ndouble: humn * two\nhumn: 3\ntwo: 2\nseven: 7\n";
+        assert_eq!(error(input), "a monkey's division leaves a remainder");
+
+        // evaluating root itself divides 7 by 2
+        let input = "root: half + one\nhalf: seven / two\nhumn: 3\none: 1\nseven: 7\ntwo: 2\n";
+        assert_eq!(error(input), "a monkey's division leaves a remainder");
+    }
+
+    #[test]
+    fn unknown_missing() {
+        let input = "root: one + two\none: 1\ntwo: 2\nhumn: 5\n";
+        assert_eq!(error(input), "humn doesn't affect root");
+    }
 }
@@ -1,5 +1,449 @@
+use std::collections::VecDeque;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::direction::Direction4;
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::grid::Grid;
+use crate::year2022::point::Point;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Instruction {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+struct Board {
+    tiles: Grid<Tile>,
+}
+
+impl Board {
+    /// Anything past the edges of the map counts as void.
+    fn tile(&self, position: Point<i64>) -> Tile {
+        let inside = (0..self.tiles.width as i64).contains(&position.x)
+            && (0..self.tiles.height as i64).contains(&position.y);
+
+        if inside {
+            self.tiles[self.tiles.index(position.x as usize, position.y as usize)]
+        } else {
+            Tile::Void
+        }
+    }
+}
+
+fn parse_notes(input: &str) -> Result<(Board, Vec<Instruction>), ParseError> {
+    let (map, path) = input
+        .split_once("\n\n")
+        .ok_or("expected the map and the path separated by a blank line")
+        .on_line(0, input.lines().next().unwrap_or(""))?;
+
+    let rows: Vec<&str> = map.lines().collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut tiles = Grid::new(width, rows.len(), || Tile::Void);
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.char_indices() {
+            let index = tiles.index(x, y);
+            tiles[index] = match c {
+                ' ' => Tile::Void,
+                '.' => Tile::Open,
+                '#' => Tile::Wall,
+                _ => {
+                    return Err("expected ' ', '.' or '#'").at(y, row, &row[x..x + c.len_utf8()])
+                }
+            };
+        }
+    }
+
+    let instructions = parse_path(rows.len() + 1, path.trim_end())?;
+    Ok((Board { tiles }, instructions))
+}
+
+/// Reads a path like `10R5L5`, where `index` is its line in the input.
+fn parse_path(index: usize, path: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions = Vec::new();
+    let mut distance_start = None;
+
+    let forward = |distance: &str| {
+        distance.parse().map(Instruction::Forward).at(index, path, distance)
+    };
+
+    for (i, c) in path.char_indices() {
+        if c.is_ascii_digit() {
+            distance_start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(start) = distance_start.take() {
+            instructions.push(forward(&path[start..i])?);
+        }
+        instructions.push(match c {
+            'L' => Instruction::Left,
+            'R' => Instruction::Right,
+            _ => {
+                return Err("expected a distance, 'L' or 'R'")
+                    .at(index, path, &path[i..i + c.len_utf8()])
+            }
+        });
+    }
+    if let Some(start) = distance_start {
+        instructions.push(forward(&path[start..])?);
+    }
+
+    Ok(instructions)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct State {
+    position: Point<i64>,
+    facing: Direction4,
+}
+
+impl State {
+    fn password(&self) -> i64 {
+        let facing = match self.facing {
+            Direction4::East => 0,
+            Direction4::South => 1,
+            Direction4::West => 2,
+            Direction4::North => 3,
+        };
+
+        1000 * (self.position.y + 1) + 4 * (self.position.x + 1) + facing
+    }
+}
+
+/// Follows the path from the leftmost open tile of the top row. `wrap` says where a step off the
+/// edge of the map lands and which way it faces afterwards.
+fn walk<W>(board: &Board, path: &[Instruction], wrap: W) -> Result<State, &'static str>
+where
+    W: Fn(State) -> State,
+{
+    let start = (0..board.tiles.width as i64)
+        .map(|x| Point::new(x, 0))
+        .find(|&position| board.tile(position) == Tile::Open)
+        .ok_or("no open tile on the top row to start from")?;
+
+    let mut state = State {
+        position: start,
+        facing: Direction4::East,
+    };
+
+    for &instruction in path {
+        match instruction {
+            Instruction::Left => state.facing = state.facing.turn_left(),
+            Instruction::Right => state.facing = state.facing.turn_right(),
+            Instruction::Forward(distance) => {
+                for _ in 0..distance {
+                    let mut next = State {
+                        position: state.position + state.facing.to_point_y_down(),
+                        ..state
+                    };
+                    if board.tile(next.position) == Tile::Void {
+                        next = wrap(state);
+                    }
+
+                    if board.tile(next.position) == Tile::Wall {
+                        break;
+                    }
+                    state = next;
+                }
+            }
+        }
+    }
+
+    Ok(state)
+}
+
+/// Comes back in on the opposite side of the same row or column.
+fn wrap_flat(board: &Board, state: State) -> State {
+    let back = state.facing.turn_around().to_point_y_down();
+
+    let mut position = state.position;
+    while board.tile(position + back) != Tile::Void {
+        position += back;
+    }
+
+    State { position, ..state }
+}
+
+type Vector = [i64; 3];
+
+fn negate(vector: Vector) -> Vector {
+    vector.map(|value| -value)
+}
+
+fn dot(a: Vector, b: Vector) -> i64 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+fn scale(vector: Vector, factor: i64) -> Vector {
+    vector.map(|value| value * factor)
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+/// Where one square of the net ends up once folded, as the directions its map right and map
+/// down point in and the way it faces out of the cube.
+#[derive(Debug, Copy, Clone)]
+struct Face {
+    corner: Point<i64>,
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+
+impl Face {
+    fn direction(&self, facing: Direction4) -> Vector {
+        match facing {
+            Direction4::East => self.right,
+            Direction4::West => negate(self.right),
+            Direction4::South => self.down,
+            Direction4::North => negate(self.down),
+        }
+    }
+
+    /// The face next to this one on the net in `facing`, folded over their shared edge.
+    fn roll(&self, facing: Direction4, corner: Point<i64>) -> Face {
+        let (right, down) = match facing {
+            Direction4::East => (negate(self.normal), self.down),
+            Direction4::West => (self.normal, self.down),
+            Direction4::South => (self.right, negate(self.normal)),
+            Direction4::North => (self.right, self.normal),
+        };
+
+        Face {
+            corner,
+            right,
+            down,
+            normal: self.direction(facing),
+        }
+    }
+}
+
+struct Cube {
+    size: i64,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Folds the map along the edges of its squares, working out which edges meet from the
+    /// shape of the net.
+    fn fold(board: &Board) -> Result<Cube, &'static str> {
+        let area = board.tiles.iter().filter(|&&tile| tile != Tile::Void).count() as i64;
+        let size = (1..).find(|size| 6 * size * size >= area).unwrap();
+        if 6 * size * size != area {
+            return Err("the map's area doesn't fit six square faces");
+        }
+
+        let is_face = |corner: Point<i64>| board.tile(corner) != Tile::Void;
+        let squares: Vec<Point<i64>> = (0..board.tiles.height as i64)
+            .step_by(size as usize)
+            .flat_map(|y| {
+                (0..board.tiles.width as i64)
+                    .step_by(size as usize)
+                    .map(move |x| Point::new(x, y))
+            })
+            .filter(|&corner| is_face(corner))
+            .collect();
+
+        let whole_square = |corner: Point<i64>| {
+            (0..size).all(|dy| (0..size).all(|dx| is_face(corner + Point::new(dx, dy))))
+        };
+        if squares.len() != 6 || !squares.iter().all(|&corner| whole_square(corner)) {
+            return Err("the map isn't made of six whole square faces");
+        }
+
+        let first = Face {
+            corner: squares[0],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+            normal: [0, 0, 1],
+        };
+
+        let mut faces = vec![first];
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            for facing in Direction4::iter() {
+                let corner = face.corner + scale_point(facing.to_point_y_down(), size);
+                let known = faces.iter().any(|face| face.corner == corner);
+                if squares.contains(&corner) && !known {
+                    let next = face.roll(facing, corner);
+                    faces.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut normals: Vec<Vector> = faces.iter().map(|face| face.normal).collect();
+        normals.sort_unstable();
+        normals.dedup();
+        if faces.len() != 6 || normals.len() != 6 {
+            return Err("the map's faces don't fold into a cube");
+        }
+
+        Ok(Cube { size, faces })
+    }
+
+    fn face_at(&self, position: Point<i64>) -> &Face {
+        self.faces
+            .iter()
+            .find(|face| {
+                let offset = position - face.corner;
+                (0..self.size).contains(&offset.x) && (0..self.size).contains(&offset.y)
+            })
+            .unwrap()
+    }
+
+    /// Carries on over the edge of the cube onto the neighbouring face.
+    fn wrap(&self, state: State) -> State {
+        let from = self.face_at(state.position);
+        let edge = from.direction(state.facing);
+        let to = self.faces.iter().find(|face| face.normal == edge).unwrap();
+
+        // doubled coordinates measured from the middle of the cube keep every tile centre whole
+        let local = state.position - from.corner;
+        let middle = self.size - 1;
+        let centre = add(
+            add(scale(from.right, 2 * local.x - middle), scale(from.down, 2 * local.y - middle)),
+            scale(from.normal, self.size),
+        );
+
+        // the tile is just past the new face's edge along one axis, pulling it in lands on the edge
+        let along = |axis: Vector| (dot(centre, axis).clamp(-middle, middle) + middle) / 2;
+        let position = to.corner + Point::new(along(to.right), along(to.down));
+
+        let heading = negate(from.normal);
+        let facing = Direction4::iter().find(|&facing| to.direction(facing) == heading).unwrap();
+
+        State { position, facing }
+    }
+}
+
+fn scale_point(point: Point<i64>, factor: i64) -> Point<i64> {
+    Point::new(point.x * factor, point.y * factor)
+}
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let (board, path) = parse_notes(input)?;
+
+    let flat = walk(&board, &path, |state| wrap_flat(&board, state))?;
+
+    let cube = Cube::fold(&board)?;
+    let folded = walk(&board, &path, |state| cube.wrap(state))?;
+
+    Ok(Solution::from(flat.password(), folded.password()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    /// An open map with faces of `size` tiles wherever the layout has a `#`.
+    fn net(layout: &[&str], size: usize) -> Board {
+        let map: String = layout
+            .iter()
+            .flat_map(|row| {
+                let line: String = row
+                    .chars()
+                    .map(|square| if square == '#' { ".".repeat(size) } else { " ".repeat(size) })
+                    .collect();
+                std::iter::repeat_n(line.trim_end().to_string() + "\n", size)
+            })
+            .collect();
+
+        parse_notes(&(map + "\n1\n")).unwrap().0
+    }
+
+    /// Steps off every edge tile of every face, turns around and steps back, which has to land
+    /// exactly where it started.
+    fn assert_round_trips(board: &Board) {
+        let cube = Cube::fold(board).unwrap();
+        let mut edges = 0;
+
+        for face in cube.faces.iter() {
+            for facing in Direction4::iter() {
+                for i in 0..cube.size {
+                    let last = cube.size - 1;
+                    let offset = match facing {
+                        Direction4::North => Point::new(i, 0),
+                        Direction4::South => Point::new(i, last),
+                        Direction4::West => Point::new(0, i),
+                        Direction4::East => Point::new(last, i),
+                    };
+                    let start = State { position: face.corner + offset, facing };
+
+                    let ahead = start.position + facing.to_point_y_down();
+                    if board.tile(ahead) != Tile::Void {
+                        continue;
+                    }
+                    edges += 1;
+
+                    let over = cube.wrap(start);
+                    assert_ne!(board.tile(over.position), Tile::Void, "from {:?}", start);
+
+                    let back = cube.wrap(State { facing: over.facing.turn_around(), ..over });
+                    let expected = State { facing: facing.turn_around(), ..start };
+                    assert_eq!(back, expected, "from {:?} via {:?}", start, over);
+                }
+            }
+        }
+
+        // seven edges are glued together, each seen from both sides
+        assert_eq!(edges as i64, 14 * cube.size);
+    }
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (6032.into(), 5031.into()));
+    }
+
+    #[test]
+    fn example_net_round_trips() {
+        let (board, _) = parse_notes(EXAMPLE).unwrap();
+        assert_round_trips(&board);
+    }
+
+    #[test]
+    fn input_net_round_trips() {
+        // the layout every real input uses, with smaller faces
+        assert_round_trips(&net(&[" ##", " # ", "## ", "#  "], 5));
+    }
+
+    #[test]
+    fn other_nets_round_trip() {
+        assert_round_trips(&net(&["#   ", "####", "#   "], 3));
+        assert_round_trips(&net(&["##  ", " ## ", "  ##"], 2));
+        assert_round_trips(&net(&["###  ", "  ###"], 4));
+    }
+
+    #[test]
+    fn rejects_maps_that_dont_fold() {
+        assert!(Cube::fold(&net(&["######"], 2)).is_err());
+        assert!(Cube::fold(&net(&["####", "##  "], 2)).is_err());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::challenge_result::{ChallengeResult, Solution};
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    Ok(Solution::unsolved())
 }
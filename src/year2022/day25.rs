@@ -1,5 +1,51 @@
-use crate::challenge_result::{ChallengeResult, Solution};
+use crate::challenge_result::{Answer, ChallengeResult, Solution};
+use crate::parse_error::Context;
+use crate::year2022::numeral::BalancedBase;
 
 pub fn run(input: &str) -> ChallengeResult {
-    Ok(Solution::from(0, 0))
+    let snafu = BalancedBase::snafu();
+
+    let total = input.lines().enumerate().try_fold(0i64, |total, (i, line)| {
+        let value = snafu.parse(line).on_line(i, line)?;
+        total
+            .checked_add(value)
+            .ok_or("the total no longer fits in 64 bits")
+            .on_line(i, line)
+    })?;
+
+    // there is no second puzzle on the last day, its star comes from finishing all the others
+    Ok(Solution::new(snafu.format(total).into(), Answer::Text(String::from("Merry Christmas!"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1=-0-2\n12111\n2=0=\n21\n2=01\n111\n20012\n112\n1=-1=\n1-12\n12\n1=\n122\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!(solution.part1, Answer::from("2=-1=0"));
+        assert_eq!(solution.part2, Answer::from("Merry Christmas!"));
+    }
+
+    #[test]
+    fn reports_bad_digit() {
+        let error = run("1=-0-2\n12311\n").unwrap_err().to_string();
+        assert!(error.starts_with("'3' is not a digit of this base on line 2"), "{}", error);
+    }
+
+    #[test]
+    fn reports_overflowing_total() {
+        let largest = BalancedBase::snafu().format(i64::MAX);
+
+        // only the running total matters, so a smaller number first leaves room
+        let input = format!("-\n{}\n1\n", largest);
+        assert_eq!(run(&input).unwrap().part1, Answer::from(largest.as_str()));
+
+        let input = format!("{}\n1\n", largest);
+        let error = run(&input).unwrap_err().to_string();
+        assert!(error.starts_with("the total no longer fits in 64 bits on line 2"), "{}", error);
+    }
 }
@@ -1,26 +1,48 @@
 use std::collections::BTreeSet;
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
 
 pub fn run(input: &str) -> ChallengeResult {
-    let lines: Vec<&str> = input.lines().collect();
+    let lines = parse_rucksacks(input)?;
 
-    let part1: u32 = lines.iter().map(|line| {
-        let half_length = line.len() / 2;
-        let first = &line[..half_length];
-        let second = &line[half_length..];
+    let part1 = lines.iter().enumerate().map(|(i, line)| {
+        let (first, second) = line.split_at(line.len() / 2);
 
-        let letter = intersection_two_ways(first, second).unwrap();
-        letter_to_int(letter)
-    }).sum();
+        let letter = intersection_two_ways(first, second)
+            .ok_or("compartments have no item in common")
+            .on_line(i, line)?;
+        Ok(letter_to_int(letter))
+    }).sum::<Result<u32, ParseError>>()?;
 
-    let part2: u32 = lines.chunks(3).map(|chunk| {
-        let letter = intersection_three_ways(chunk[0], chunk[1], chunk[2]).unwrap();
-        letter_to_int(letter)
-    }).sum();
+    let part2 = lines.chunks(3).enumerate().map(|(group, chunk)| {
+        let last = 3 * group + chunk.len() - 1;
+        let letter = match chunk {
+            &[first, second, third] => intersection_three_ways(first, second, third)
+                .ok_or("group has no badge in common"),
+            _ => Err("expected elves in groups of three"),
+        };
+        Ok(letter_to_int(letter.on_line(last, chunk[chunk.len() - 1])?))
+    }).sum::<Result<u32, ParseError>>()?;
 
     Ok(Solution::from(part1, part2))
 }
 
+fn parse_rucksacks(input: &str) -> Result<Vec<&str>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if let Some(item) = line.matches(|c: char| !c.is_ascii_alphabetic()).next() {
+                return Err("expected items a-z or A-Z").at(i, line, item);
+            }
+            if line.len() % 2 != 0 {
+                return Err("expected two compartments of the same size").on_line(i, line);
+            }
+            Ok(line)
+        })
+        .collect()
+}
+
 fn letter_to_int(c: char) -> u32 {
     let mut value = c as u32 - 38;
     if value > 58 {
@@ -52,4 +74,34 @@ fn intersection_three_ways(first: &str, second: &str, third: &str) -> Option<cha
     }
 
     None
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (157.into(), 70.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nabc1ef\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 4));
+        assert!(error.to_string().ends_with("2 | abc1ef\n  |    ^"), "{}", error);
+
+        let error = parse_rucksacks("abc").unwrap_err();
+        assert!(error.to_string().ends_with("1 | abc\n  | ^^^"), "{}", error);
+    }
+}
@@ -1,43 +1,58 @@
 use crate::challenge_result::{ChallengeResult, Solution};
-
-#[derive(Copy, Clone)]
-struct Range {
-    start: u32,
-    finish: u32,
-}
-
-impl Range {
-    fn new(start: u32, finish: u32) -> Range {
-        Range { start, finish }
-    }
-
-    fn contains(&self, other: Range) -> bool {
-        self.start <= other.start && self.finish >= other.finish
-    }
-
-    fn overlaps(&self, other: Range) -> bool {
-        (self.start <= other.start && other.start <= self.finish) ||
-            (other.start <= self.start && self.start <= other.finish)
-    }
-}
+use crate::parse_error::{Context, ParseError};
+use crate::year2022::interval::Interval;
 
 pub fn run(input: &str) -> ChallengeResult {
-    let data: Vec<_> = input.lines().map(|line| {
-        let mut ranges = line.split(",").map(| range | {
-            let mut parts = range.split("-").flat_map(| part | part.parse::<u32>().ok());
-            Range::new(parts.next().unwrap(), parts.next().unwrap())
-        });
-
-        (ranges.next().unwrap(), ranges.next().unwrap())
-    }).collect();
+    let data: Vec<_> = input.lines().enumerate().map(|(i, line)| {
+        let parse_range = | range: &str | {
+            let (start, finish) = range.split_once('-')
+                .ok_or("expected \"start-finish\"")
+                .at(i, line, range)?;
+
+            Ok(Interval::<u32>::new(
+                start.parse().at(i, line, start)?,
+                finish.parse().at(i, line, finish)?,
+            ))
+        };
+
+        let (first, second) = line.split_once(',')
+            .ok_or("expected two ranges")
+            .on_line(i, line)?;
+        Ok((parse_range(first)?, parse_range(second)?))
+    }).collect::<Result<_, ParseError>>()?;
 
     let part1 = data.iter().filter(|(first, second)| {
-        first.contains(*second) || second.contains(*first)
+        first.contains_interval(second) || second.contains_interval(first)
     }).count();
 
     let part2 = data.iter().filter(|(first, second)| {
-        first.overlaps(*second)
+        first.overlaps(second)
     }).count();
 
     Ok(Solution::from(part1, part2))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (2.into(), 4.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = run("2-4,6-8\n2-3,4x5\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected \"start-finish\" on line 2, column 5\n2 | 2-3,4x5\n  |     ^^^"
+        );
+
+        let error = run("2-4,6-z").unwrap_err();
+        assert!(error.to_string().ends_with("1 | 2-4,6-z\n  |       ^"), "{}", error);
+    }
+}
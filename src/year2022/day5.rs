@@ -1,4 +1,5 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
 
 #[derive(Clone, Debug)]
 struct Move {
@@ -9,12 +10,18 @@ struct Move {
 
 pub fn run(input: &str) -> ChallengeResult {
     let lines: Vec<&str> = input.lines().collect();
+    let blank = lines
+        .iter()
+        .position(|line| line.is_empty())
+        .ok_or("expected a blank line between the stacks and the moves")?;
 
-    let stacks = parse_stacks(&lines[..8]);
-    let moves = parse_moves(&lines[10..]);
+    let (labels, crates) = lines[..blank].split_last().ok_or("expected a drawing of the stacks")?;
+    let stack_count = labels.split_whitespace().count();
 
+    let stacks = parse_stacks(crates, stack_count);
+    let moves = parse_moves(&lines[blank + 1..], blank + 1, stack_count)?;
 
-    Ok(Solution::new(part1(&stacks, &moves), part2(&stacks, &moves)))
+    Ok(Solution::from(part1(&stacks, &moves), part2(&stacks, &moves)))
 }
 
 fn part1(stacks: &Vec<Vec<char>>, moves: &Vec<Move>) -> String {
@@ -47,39 +54,80 @@ fn part2(stacks: &Vec<Vec<char>>, moves: &Vec<Move>) -> String {
     stacks.iter().flat_map(| stack | stack.last()).collect()
 }
 
-fn parse_stacks(input: &[&str]) -> Vec<Vec<char>> {
+fn parse_stacks(input: &[&str], stack_count: usize) -> Vec<Vec<char>> {
     let lines: Vec<Vec<_>> = input
         .iter()
         .rev()
         .map(|line| line.chars().skip(1).step_by(4).collect())
         .collect();
 
-    (0..9)
+    (0..stack_count)
         .map(|i| {
             lines
                 .iter()
-                .map(|line| line[i])
+                .flat_map(|line| line.get(i))
+                .cloned()
                 .filter(|&c| c != ' ')
                 .collect()
         })
         .collect()
 }
 
-fn parse_moves(input: &[&str]) -> Vec<Move> {
-    input
+fn parse_moves(lines: &[&str], first_line: usize, stack_count: usize) -> Result<Vec<Move>, ParseError> {
+    lines
         .iter()
-        .map(|line| {
-            let mut data = line
-                .split(' ')
-                .skip(1)
-                .step_by(2)
-                .map(|x| x.parse::<usize>().unwrap());
-
-            Move {
-                amount: data.next().unwrap(),
-                from: data.next().unwrap() - 1,
-                to: data.next().unwrap() - 1,
+        .enumerate()
+        .map(|(i, line)| {
+            let index = first_line + i;
+            let stack = |part: &str| {
+                part.parse::<usize>()
+                    .ok()
+                    .filter(|stack| (1..=stack_count).contains(stack))
+                    .map(|stack| stack - 1)
+                    .ok_or_else(|| format!("stack must be between 1 and {}", stack_count))
+                    .at(index, line, part)
+            };
+
+            match line.split(' ').collect::<Vec<_>>()[..] {
+                ["move", amount, "from", from, "to", to] => Ok(Move {
+                    amount: amount.parse().at(index, line, amount)?,
+                    from: stack(from)?,
+                    to: stack(to)?,
+                }),
+                _ => Err("expected \"move <amount> from <stack> to <stack>\"").on_line(index, line),
             }
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), ("CMZ".into(), "MCD".into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_moves(&["move 1 from 2 to 1", "move 3 from 4 to 3"], 5, 3).unwrap_err();
+        assert_eq!((error.line(), error.column()), (7, 13));
+        assert!(error.to_string().ends_with("7 | move 3 from 4 to 3\n  |             ^"), "{}", error);
+
+        let error = parse_moves(&["move 1 from 2"], 5, 3).unwrap_err();
+        assert!(error.to_string().ends_with("6 | move 1 from 2\n  | ^^^^^^^^^^^^^"), "{}", error);
+    }
+}
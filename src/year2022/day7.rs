@@ -1,4 +1,5 @@
 use crate::challenge_result::{ChallengeResult, Solution};
+use crate::parse_error::{Context, ParseError};
 
 struct Directory<'a> {
     name: &'a str,
@@ -74,40 +75,51 @@ impl<'a> FileSystem<'a> {
     }
 }
 
-fn parse_filesystem(input: &str) -> FileSystem {
+fn parse_filesystem(input: &str) -> Result<FileSystem<'_>, ParseError> {
     let mut current_dir = 0;
     let mut filesystem = FileSystem::new();
+    // total size of the files listed so far, while reading the output of an ls
+    let mut listing: Option<u32> = None;
 
-    for command in input.split("$ ").skip(1) {
-        let mut lines = command.lines();
-
-        match lines.next().expect("No command.") {
-            "ls" => {
-                let size = lines
-                    .flat_map(|parts| parts.split(" ").next())
-                    .flat_map(|first_part| first_part.parse::<u32>().ok())
-                    .sum();
-                filesystem.set_dir_size(current_dir, size)
-            }
-            "cd /" => {
-                current_dir = 0;
+    for (i, line) in input.lines().enumerate() {
+        if let Some(command) = line.strip_prefix("$ ") {
+            if let Some(size) = listing.take() {
+                filesystem.set_dir_size(current_dir, size);
             }
-            "cd .." => {
-                if let Some(next_dir) = filesystem.get_parent(current_dir) {
-                    current_dir = next_dir
+
+            match command.split_once(' ') {
+                None if command == "ls" => listing = Some(0),
+                Some(("cd", "/")) => current_dir = 0,
+                Some(("cd", "..")) => {
+                    if let Some(next_dir) = filesystem.get_parent(current_dir) {
+                        current_dir = next_dir
+                    }
+                }
+                Some(("cd", name)) => {
+                    current_dir = filesystem.get_or_make_dir(current_dir, name);
                 }
+                _ => return Err("expected \"cd <dir>\" or \"ls\"").at(i, line, command),
             }
-            cd => {
-                current_dir = filesystem.get_or_make_dir(current_dir, &cd[3..]);
+        } else {
+            let size = listing.as_mut().ok_or("expected a command").on_line(i, line)?;
+            match line.split_once(' ') {
+                Some(("dir", _)) => {}
+                Some((file_size, _)) => *size += file_size.parse::<u32>().at(i, line, file_size)?,
+                None => {
+                    return Err("expected \"dir <name>\" or \"<size> <name>\"").on_line(i, line)
+                }
             }
         }
     }
 
-    filesystem
+    if let Some(size) = listing {
+        filesystem.set_dir_size(current_dir, size);
+    }
+    Ok(filesystem)
 }
 
 pub fn run(input: &str) -> ChallengeResult {
-    let filesystem = parse_filesystem(input);
+    let filesystem = parse_filesystem(input)?;
 
     let part1 = filesystem
         .directories
@@ -119,13 +131,65 @@ pub fn run(input: &str) -> ChallengeResult {
     let total_space = 70000000;
     let target_space = 30000000;
     let used_space = filesystem.directories[0].files_size;
-    let need_to_free = used_space - (total_space - target_space);
+    let need_to_free = used_space
+        .checked_sub(total_space - target_space)
+        .ok_or("there's already enough free space")?;
 
     let part2 = filesystem.directories.iter()
         .map(|dir| dir.files_size)
         .filter(|&files_size| files_size > need_to_free)
         .min()
-        .expect("No answer for part2");
+        .ok_or("no directory is large enough to free the space needed")?;
 
     Ok(Solution::from(part1, part2))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn example() {
+        let solution = run(EXAMPLE).unwrap();
+        assert_eq!((solution.part1, solution.part2), (95437.into(), 24933642.into()));
+    }
+
+    #[test]
+    fn bad_lines() {
+        let error = parse_filesystem("$ cd /\n$ mv a b\n").err().unwrap();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert!(error.to_string().ends_with("2 | $ mv a b\n  |   ^^^^^^"), "{}", error);
+
+        let error = parse_filesystem("$ ls\n12k b.txt\n").err().unwrap();
+        assert!(error.to_string().ends_with("2 | 12k b.txt\n  | ^^^"), "{}", error);
+
+        let error = parse_filesystem("dir a\n").err().unwrap();
+        assert!(error.to_string().starts_with("expected a command on line 1, column 1"));
+    }
+}
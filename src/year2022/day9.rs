@@ -4,56 +4,13 @@ use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use std::str::FromStr;
 use crate::challenge_result::{ChallengeResult, Solution};
-use crate::year2022::error::ParseLineError;
+use crate::direction::{Direction4, ParseDirectionError};
+use crate::parse_error::parse_lines;
 use crate::year2022::point::Point;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn to_point(&self) -> Point<i8> {
-        match self {
-            Direction::Up => Point::new(0, 1),
-            Direction::Down => Point::new(0, -1),
-            Direction::Left => Point::new(-1, 0),
-            Direction::Right => Point::new(1, 0),
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-struct ParseDirectionError;
-
-impl Display for ParseDirectionError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "direction must be 'U', 'D', 'L' or 'R'")
-    }
-}
-
-impl Error for ParseDirectionError {}
-
-impl FromStr for Direction {
-    type Err = ParseDirectionError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "U" => Ok(Direction::Up),
-            "D" => Ok(Direction::Down),
-            "L" => Ok(Direction::Left),
-            "R" => Ok(Direction::Right),
-            _ => Err(ParseDirectionError)
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone)]
 struct Move {
-    direction: Direction,
+    direction: Direction4,
     distance: i8,
 }
 
@@ -78,11 +35,11 @@ impl FromStr for Move {
     type Err = ParseMoveError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, right) = s.split_at(1);
+        let (left, right) = s.split_once(' ').unwrap_or((s, ""));
 
         Ok(Move {
             direction: left.parse().map_err(ParseMoveError::Direction)?,
-            distance: right[1..].parse().map_err(ParseMoveError::Distance)?,
+            distance: right.parse().map_err(ParseMoveError::Distance)?,
         })
     }
 }
@@ -115,7 +72,7 @@ fn part1(moves: &[Move]) -> usize {
     let mut relative_tail = Point::new(0, 0);
 
     for &Move { direction, distance } in moves {
-        let direction = direction.to_point();
+        let direction = direction.to_point_y_up();
 
         for _ in 0..distance {
             head += direction;
@@ -135,7 +92,7 @@ fn part2(moves: &[Move]) -> usize {
     let mut tails: [Point<i8>; 9] = Default::default();
 
     for &Move { direction, distance } in moves {
-        let direction = direction.to_point();
+        let direction = direction.to_point_y_up();
 
         'step_loop: for _ in 0..distance {
             head += direction;
@@ -162,13 +119,7 @@ fn part2(moves: &[Move]) -> usize {
 }
 
 pub fn run(input: &str) -> ChallengeResult {
-    let moves: Vec<Move> = input
-        .lines()
-        .enumerate()
-        .map(|(i, line) | line.parse().map_err(| err |
-            ParseLineError::new(i, err)
-        ))
-        .collect::<Result<_, _>>()?;
+    let moves: Vec<Move> = parse_lines(input)?;
 
     Ok(Solution::from(part1(&moves), part2(&moves)))
 }
@@ -4,6 +4,7 @@ use std::iter::StepBy;
 use std::ops::{Index, IndexMut};
 use std::slice::{Iter, IterMut};
 use std::str::FromStr;
+use crate::year2022::point::Point;
 
 pub struct Grid<T> {
     pub width: usize,
@@ -38,6 +39,17 @@ impl<T> Grid<T> {
         x + y * self.width
     }
 
+    pub fn draw<I>(&mut self, points: I, value: T)
+    where
+        I: IntoIterator<Item = Point<usize>>,
+        T: Clone,
+    {
+        for point in points {
+            let index = self.index(point.x, point.y);
+            self.data[index] = value.clone();
+        }
+    }
+
     pub fn iter(&self) -> Iter<T> {
         self.data.iter()
     }
@@ -0,0 +1,315 @@
+#![allow(dead_code)]
+
+use std::fmt::{Display, Formatter};
+use std::slice::Iter;
+use num::PrimInt;
+
+/// A range of integers where both ends are inclusive. `start` is never after `end`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// The values between `a` and `b` inclusive, in whichever order they're given.
+    pub fn new(a: T, b: T) -> Interval<T> {
+        Interval {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// How many values the interval holds, or `None` if that count doesn't fit in `T`, as for
+    /// the full range of the type.
+    pub fn len(&self) -> Option<T> {
+        self.end.checked_sub(&self.start)?.checked_add(&T::one())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Overlapping or directly next to each other, so the union is a single interval.
+    fn touches(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end.saturating_add(T::one()) &&
+            other.start <= self.end.saturating_add(T::one())
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(other) {
+            Some(Interval::new(self.start.max(other.start), self.end.min(other.end)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Disjoint intervals kept sorted, with overlapping and adjacent intervals merged on insert.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self.intervals
+            .partition_point(|existing| existing.end.saturating_add(T::one()) < interval.start);
+        let last = first + self.intervals[first..]
+            .iter()
+            .take_while(|existing| existing.touches(&interval))
+            .count();
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, existing| {
+                Interval::new(merged.start.min(existing.start), merged.end.max(existing.end))
+            });
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|interval| interval.end < value);
+        matches!(self.intervals.get(index), Some(interval) if interval.contains(value))
+    }
+
+    /// Total count of values covered by the set, or `None` if that count doesn't fit in `T`.
+    pub fn covered(&self) -> Option<T> {
+        self.intervals
+            .iter()
+            .try_fold(T::zero(), |total, interval| total.checked_add(&interval.len()?))
+    }
+
+    /// Every uncovered interval within `bounds`.
+    pub fn gaps(&self, bounds: Interval<T>) -> Vec<Interval<T>> {
+        let mut gaps = Vec::new();
+        let mut next = bounds.start;
+
+        for interval in self.intervals.iter().filter(|interval| interval.overlaps(&bounds)) {
+            if next < interval.start {
+                gaps.push(Interval::new(next, interval.start - T::one()));
+            }
+            if interval.end >= bounds.end {
+                return gaps;
+            }
+            next = next.max(interval.end + T::one());
+        }
+
+        gaps.push(Interval::new(next, bounds.end));
+        gaps
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let mut remaining = Vec::with_capacity(self.intervals.len() + 1);
+
+        for &existing in self.intervals.iter() {
+            if !existing.overlaps(&interval) {
+                remaining.push(existing);
+                continue;
+            }
+
+            if existing.start < interval.start {
+                remaining.push(Interval::new(existing.start, interval.start - T::one()));
+            }
+            if interval.end < existing.end {
+                remaining.push(Interval::new(interval.end + T::one(), existing.end));
+            }
+        }
+
+        self.intervals = remaining;
+    }
+
+    pub fn subtract(&mut self, other: &IntervalSet<T>) {
+        for &interval in other.iter() {
+            self.remove(interval);
+        }
+    }
+
+    /// The values covered by both sets.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut common = Vec::new();
+        let mut mine = self.intervals.iter().peekable();
+        let mut theirs = other.intervals.iter().peekable();
+
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            // both sets are sorted and gapped, so the overlaps come out sorted and gapped too
+            if let Some(overlap) = a.intersection(b) {
+                common.push(overlap);
+            }
+
+            // whichever ends first can't overlap anything further along the other set
+            if a.end < b.end {
+                mine.next();
+            } else {
+                theirs.next();
+            }
+        }
+
+        IntervalSet { intervals: common }
+    }
+
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|&(start, end)| Interval::new(start, end)).collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|interval| (interval.start, interval.end)).collect()
+    }
+
+    #[test]
+    fn interval_relations() {
+        let a = Interval::new(2, 6);
+        assert_eq!(a.len(), Some(5));
+        assert!(a.contains_interval(&Interval::new(3, 6)));
+        assert!(a.overlaps(&Interval::new(6, 8)));
+        assert!(!a.overlaps(&Interval::new(7, 8)));
+        assert_eq!(a.intersection(&Interval::new(4, 9)), Some(Interval::new(4, 6)));
+        assert_eq!(a.intersection(&Interval::new(7, 9)), None);
+    }
+
+    #[test]
+    fn inverted_bounds_are_swapped() {
+        let interval = Interval::new(9u32, 3);
+        assert_eq!((interval.start, interval.end), (3, 9));
+        assert_eq!(interval.len(), Some(7));
+        assert!(interval.contains(5));
+
+        let intervals: IntervalSet<u32> =
+            [Interval::new(12, 10), Interval::new(8, 4)].into_iter().collect();
+        assert_eq!(intervals.covered(), Some(8));
+        assert_eq!(
+            intervals.gaps(Interval::new(14, 0)),
+            [Interval::new(0, 3), Interval::new(9, 9), Interval::new(13, 14)]
+        );
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent() {
+        let mut intervals = set(&[(10, 12), (1, 3), (20, 25)]);
+        assert_eq!(pairs(&intervals), [(1, 3), (10, 12), (20, 25)]);
+
+        intervals.insert(Interval::new(4, 9));
+        assert_eq!(pairs(&intervals), [(1, 12), (20, 25)]);
+
+        intervals.insert(Interval::new(11, 21));
+        assert_eq!(pairs(&intervals), [(1, 25)]);
+        assert_eq!(intervals.len(), 1);
+        assert_eq!(intervals.covered(), Some(25));
+    }
+
+    #[test]
+    fn insert_at_the_type_limits() {
+        let intervals: IntervalSet<u8> =
+            [Interval::new(250, 255), Interval::new(0, 4), Interval::new(5, 249)]
+                .into_iter()
+                .collect();
+        assert_eq!(intervals.iter().collect::<Vec<_>>(), [&Interval::new(0, 255)]);
+    }
+
+    #[test]
+    fn lengths_that_overflow() {
+        assert_eq!(Interval::new(0u8, 254).len(), Some(255));
+        assert_eq!(Interval::new(0u8, 255).len(), None);
+        assert_eq!(Interval::new(i8::MIN, i8::MAX).len(), None);
+        assert_eq!(Interval::new(-64i8, 62).len(), Some(127));
+
+        let bytes = |intervals: &[(u8, u8)]| -> IntervalSet<u8> {
+            intervals.iter().map(|&(start, end)| Interval::new(start, end)).collect()
+        };
+        assert_eq!(bytes(&[(0, 99), (101, 200)]).covered(), Some(200));
+        assert_eq!(bytes(&[(0, 127), (128, 255)]).covered(), None);
+        assert_eq!(bytes(&[(0, 127), (129, 255)]).covered(), Some(255));
+    }
+
+    #[test]
+    fn remove_and_subtract() {
+        let mut intervals = set(&[(1, 10), (20, 30)]);
+        intervals.remove(Interval::new(5, 22));
+        assert_eq!(pairs(&intervals), [(1, 4), (23, 30)]);
+
+        intervals.subtract(&set(&[(1, 1), (25, 26), (29, 40)]));
+        assert_eq!(pairs(&intervals), [(2, 4), (23, 24), (27, 28)]);
+
+        intervals.subtract(&set(&[(0, 50)]));
+        assert!(intervals.is_empty());
+    }
+
+    #[test]
+    fn intersection() {
+        let a = set(&[(1, 5), (8, 12), (20, 30)]);
+        let b = set(&[(0, 2), (4, 9), (11, 22), (29, 29), (40, 50)]);
+        let expected = [(1, 2), (4, 5), (8, 9), (11, 12), (20, 22), (29, 29)];
+        assert_eq!(pairs(&a.intersection(&b)), expected);
+        assert_eq!(pairs(&b.intersection(&a)), expected);
+
+        assert!(a.intersection(&set(&[(6, 7), (13, 19)])).is_empty());
+        assert!(a.intersection(&set(&[])).is_empty());
+        assert_eq!(a.intersection(&a), a);
+    }
+
+    #[test]
+    fn contains() {
+        let intervals = set(&[(1, 3), (7, 9)]);
+        assert!(intervals.contains(1));
+        assert!(intervals.contains(9));
+        assert!(!intervals.contains(5));
+        assert!(!intervals.contains(10));
+    }
+
+    #[test]
+    fn gaps() {
+        let intervals = set(&[(-5, 2), (5, 6), (9, 30)]);
+        let gaps = intervals.gaps(Interval::new(0, 20));
+        assert_eq!(gaps, [Interval::new(3, 4), Interval::new(7, 8)]);
+
+        assert_eq!(set(&[]).gaps(Interval::new(0, 3)), [Interval::new(0, 3)]);
+        assert_eq!(set(&[(0, 1)]).gaps(Interval::new(0, 3)), [Interval::new(2, 3)]);
+        assert!(set(&[(0, 3)]).gaps(Interval::new(0, 3)).is_empty());
+    }
+}
@@ -1,31 +1,763 @@
-use num::{PrimInt};
+#![allow(dead_code)]
 
-pub fn lcm<T>(first: T, second: T) -> T
+use num::{Integer, PrimInt, Signed};
+use crate::rational::{solve_linear_system, Rational};
+
+/// Least common multiple, dividing before multiplying so only a result that itself doesn't fit
+/// in `T` overflows, which gives `None`. Zero if either argument is zero.
+pub fn checked_lcm<T>(first: T, second: T) -> Option<T>
 where
     T: PrimInt,
 {
-    first * second / gcd(first, second)
+    if first.is_zero() || second.is_zero() {
+        return Some(T::zero());
+    }
+
+    let first = checked_abs(first)?;
+    let second = checked_abs(second)?;
+    (first / gcd(first, second)).checked_mul(&second)
 }
 
+/// Greatest common divisor, always non-negative. `gcd(x, 0)` is `|x|` and `gcd(0, 0)` is zero.
+///
+/// Like `abs`, this overflows when an argument is the minimum of a signed type, which has no
+/// positive counterpart.
 pub fn gcd<T>(first: T, second: T) -> T
 where
     T: PrimInt,
 {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        let val = max;
+    let mut max = abs(first);
+    let mut min = abs(second);
+
+    while !min.is_zero() {
+        let res = max % min;
         max = min;
-        min = val;
+        min = res;
     }
 
-    loop {
-        let res = max % min;
-        if res.is_zero() {
-            return min;
+    max
+}
+
+/// Overflows on `T::min_value()` of a signed type, see `checked_abs`.
+fn abs<T: PrimInt>(value: T) -> T {
+    if value < T::zero() {
+        T::zero() - value
+    } else {
+        value
+    }
+}
+
+fn checked_abs<T: PrimInt>(value: T) -> Option<T> {
+    if value < T::zero() {
+        T::zero().checked_sub(&value)
+    } else {
+        Some(value)
+    }
+}
+
+/// Returns `(g, x, y)` where `g = gcd(a, b)` and `a * x + b * y = g`.
+///
+/// Only for signed types, since one of the Bézout coefficients is usually negative.
+pub fn extended_gcd<T>(a: T, b: T) -> (T, T, T)
+where
+    T: PrimInt + Signed,
+{
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::one(), T::zero());
+    let (mut old_t, mut t) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Non-negative remainder of `value` divided by `modulus`.
+pub fn modulo<T>(value: T, modulus: T) -> T
+where
+    T: PrimInt,
+{
+    let remainder = value % modulus;
+    if remainder < T::zero() {
+        remainder + modulus
+    } else {
+        remainder
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1`, or `None` when `a` and `modulus` share a factor
+/// or `modulus` isn't positive.
+///
+/// Only for signed types, as it goes through `extended_gcd`.
+pub fn mod_inverse<T>(a: T, modulus: T) -> Option<T>
+where
+    T: PrimInt + Signed,
+{
+    if modulus <= T::zero() {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(modulo(a, modulus), modulus);
+    if g.is_one() {
+        Some(modulo(x, modulus))
+    } else {
+        None
+    }
+}
+
+/// `(a + b) % modulus` for `a` and `b` already reduced, without overflowing.
+pub fn add_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `a * b % modulus` for `a` and `b` already reduced, falling back to double-and-add when the
+/// product would overflow.
+pub fn mul_mod<T>(a: T, b: T, modulus: T) -> T
+where
+    T: PrimInt,
+{
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+
+    let mut result = T::zero();
+    let mut a = a;
+    let mut b = b;
+    while !b.is_zero() {
+        if (b & T::one()).is_one() {
+            result = add_mod(result, a, modulus);
         }
+        a = add_mod(a, a, modulus);
+        b = b >> 1;
+    }
 
-        max = min;
-        min = res;
+    result
+}
+
+/// `base` to the power of `exponent`, reduced by `modulus`. Works for unsigned types as well as
+/// signed ones. Panics if `modulus` is zero.
+pub fn mod_pow<T>(base: T, exponent: T, modulus: T) -> T
+where
+    T: PrimInt,
+{
+    if modulus.is_one() {
+        return T::zero();
+    }
+
+    let mut result = T::one();
+    let mut base = modulo(base, modulus);
+    let mut exponent = exponent;
+    while exponent > T::zero() {
+        if (exponent & T::one()).is_one() {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent = exponent >> 1;
     }
-}
\ No newline at end of file
+
+    result
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every pair, returning `(x, lcm)` with
+/// `x` in `0..lcm`. The moduli don't need to be coprime; `None` means the congruences conflict,
+/// a modulus isn't positive or the combined modulus overflows `T`.
+///
+/// Only for signed types, as it goes through `extended_gcd`.
+pub fn crt<T>(congruences: &[(T, T)]) -> Option<(T, T)>
+where
+    T: PrimInt + Signed,
+{
+    congruences
+        .iter()
+        .try_fold((T::zero(), T::one()), |(residue, modulus), &(next_residue, next_modulus)| {
+            if next_modulus <= T::zero() {
+                return None;
+            }
+
+            let next_residue = modulo(next_residue, next_modulus);
+            let (g, inverse, _) = extended_gcd(modulus, next_modulus);
+
+            let difference = next_residue - modulo(residue, next_modulus);
+            if !(difference % g).is_zero() {
+                return None;
+            }
+
+            let step_modulus = next_modulus / g;
+            let steps = mul_mod(
+                modulo(difference / g, step_modulus),
+                modulo(inverse, step_modulus),
+                step_modulus,
+            );
+
+            let combined = checked_lcm(modulus, next_modulus)?;
+            let offset = mul_mod(modulus, steps, combined);
+            Some((add_mod(residue, offset, combined), combined))
+        })
+}
+
+const SIEVE_SEGMENT: u64 = 1 << 15;
+
+/// Every prime up to and including `limit`.
+pub fn primes_up_to(limit: u64) -> Vec<u64> {
+    primes_between(0, limit)
+}
+
+/// Every prime in `low..=high`, sieved one cache-sized segment at a time so large ranges don't
+/// need a flag for every number below `high`.
+pub fn primes_between(low: u64, high: u64) -> Vec<u64> {
+    let low = low.max(2);
+    if high < low {
+        return Vec::new();
+    }
+
+    let root = integer_sqrt(high);
+    let mut base_flags = vec![true; root as usize + 1];
+    let mut base_primes = Vec::new();
+    for i in 2..=root as usize {
+        if base_flags[i] {
+            base_primes.push(i as u64);
+            for multiple in (i * i..=root as usize).step_by(i) {
+                base_flags[multiple] = false;
+            }
+        }
+    }
+
+    let mut primes = Vec::new();
+    let mut flags = vec![true; SIEVE_SEGMENT as usize];
+    let mut segment_start = low;
+    while segment_start <= high {
+        let segment_end = high.min(segment_start.saturating_add(SIEVE_SEGMENT - 1));
+        let flags = &mut flags[..(segment_end - segment_start + 1) as usize];
+        flags.fill(true);
+
+        for &prime in base_primes.iter() {
+            let first = (prime * prime).max(segment_start.div_ceil(prime) * prime);
+            let mut multiple = first;
+            while multiple <= segment_end {
+                flags[(multiple - segment_start) as usize] = false;
+                multiple += prime;
+            }
+        }
+
+        primes.extend(
+            flags
+                .iter()
+                .zip(segment_start..)
+                .filter(|(&is_prime, _)| is_prime)
+                .map(|(_, n)| n),
+        );
+
+        if segment_end == u64::MAX {
+            break;
+        }
+        segment_start = segment_end + 1;
+    }
+
+    primes
+}
+
+pub fn integer_sqrt(n: u64) -> u64 {
+    // the float estimate can be off by one either way, but no root of a u64 exceeds u32::MAX
+    let mut root = ((n as f64).sqrt() as u64).min(u32::MAX as u64);
+    while root * root > n {
+        root -= 1;
+    }
+    while (root + 1).checked_mul(root + 1).is_some_and(|square| square <= n) {
+        root += 1;
+    }
+    root
+}
+
+fn mul_mod_u64(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn pow_mod_u64(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_u64(result, base, modulus);
+        }
+        base = mul_mod_u64(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller–Rabin; the first twelve primes as witnesses cover every `u64`.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for &witness in WITNESSES.iter() {
+        if Integer::is_multiple_of(&n, &witness) {
+            return n == witness;
+        }
+    }
+
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+
+    'witness: for &witness in WITNESSES.iter() {
+        let mut x = pow_mod_u64(witness, odd, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..shift {
+            x = mul_mod_u64(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+/// Prime factors of `n` with their multiplicity found by dividing out every candidate up to
+/// `sqrt(n)`. Fine for numbers up to around 10^12.
+pub fn trial_division(n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+
+    let mut n = n;
+
+    let mut divide_out = |n: &mut u64, divisor: u64| {
+        let mut count = 0;
+        while Integer::is_multiple_of(n, &divisor) {
+            *n /= divisor;
+            count += 1;
+        }
+        if count > 0 {
+            factors.push((divisor, count));
+        }
+    };
+
+    divide_out(&mut n, 2);
+    let mut divisor = 3;
+    while divisor <= n / divisor {
+        divide_out(&mut n, divisor);
+        divisor += 2;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+/// A non-trivial divisor of the composite `n` using Pollard's rho with Brent's cycle finding.
+fn pollard_rho(n: u64) -> u64 {
+    if Integer::is_multiple_of(&n, &2) {
+        return 2;
+    }
+
+    for increment in 1.. {
+        let step = |x: u64| add_mod(mul_mod_u64(x, x, n), increment % n, n);
+
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = 2;
+        let mut hare = step(tortoise);
+        let mut divisor = 1;
+
+        while divisor == 1 {
+            if power == length {
+                tortoise = hare;
+                power *= 2;
+                length = 0;
+            }
+            hare = step(hare);
+            length += 1;
+            divisor = gcd(tortoise.abs_diff(hare), n);
+        }
+
+        if divisor != n {
+            return divisor;
+        }
+    }
+
+    unreachable!()
+}
+
+/// Prime factors of `n` with their multiplicity, in ascending order. Small factors are found by
+/// trial division and anything left over is split with Pollard's rho.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    const TRIAL_LIMIT: u64 = 1000;
+
+    let mut primes = Vec::new();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut n = n;
+    for divisor in 2..TRIAL_LIMIT {
+        while Integer::is_multiple_of(&n, &divisor) {
+            n /= divisor;
+            primes.push(divisor);
+        }
+    }
+
+    let mut pending = vec![n];
+    while let Some(value) = pending.pop() {
+        if value == 1 {
+            continue;
+        }
+        if is_prime(value) {
+            primes.push(value);
+        } else {
+            let divisor = pollard_rho(value);
+            pending.push(divisor);
+            pending.push(value / divisor);
+        }
+    }
+
+    primes.sort_unstable();
+    primes
+        .into_iter()
+        .fold(Vec::new(), |mut factors: Vec<(u64, u32)>, prime| {
+            match factors.last_mut() {
+                Some((last, count)) if *last == prime => *count += 1,
+                _ => factors.push((prime, 1)),
+            }
+            factors
+        })
+}
+
+/// Every divisor of `n` in ascending order.
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut divisors = vec![1];
+    for (prime, count) in factorize(n) {
+        let existing = divisors.len();
+        let mut power = 1;
+        for _ in 0..count {
+            power *= prime;
+            for i in 0..existing {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+
+    divisors.sort_unstable();
+    divisors
+}
+
+/// Euler's totient: how many of `1..=n` are coprime with `n`.
+pub fn totient(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .fold(n, |result, (prime, _)| result / prime * (prime - 1))
+}
+
+/// Evaluates at `x` the unique polynomial of lowest degree passing through every sample, using
+/// Lagrange's formula. The sample `x` values must be distinct.
+pub fn lagrange_interpolate(samples: &[(Rational, Rational)], x: &Rational) -> Rational {
+    samples
+        .iter()
+        .enumerate()
+        .fold(Rational::zero(), |total, (i, (xi, yi))| {
+            let basis = samples
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Rational::one(), |basis, (_, (xj, _))| {
+                    basis * (x.clone() - xj.clone()) / (xi.clone() - xj.clone())
+                });
+
+            total + yi.clone() * basis
+        })
+}
+
+/// Rows of repeated differences, starting from the sequence itself and stopping at the first
+/// row that is all zero.
+fn difference_table(sequence: &[i64]) -> Vec<Vec<i64>> {
+    let mut table = vec![sequence.to_vec()];
+
+    while let Some(last) = table.last() {
+        if last.iter().all(|&value| value == 0) {
+            break;
+        }
+        let next: Vec<i64> = last.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        table.push(next);
+    }
+
+    table
+}
+
+/// The value following the sequence, assuming its finite differences eventually reach zero.
+pub fn extrapolate_forward(sequence: &[i64]) -> i64 {
+    difference_table(sequence)
+        .iter()
+        .filter_map(|row| row.last())
+        .sum()
+}
+
+/// The value preceding the sequence, assuming its finite differences eventually reach zero.
+pub fn extrapolate_backward(sequence: &[i64]) -> i64 {
+    difference_table(sequence)
+        .iter()
+        .rev()
+        .filter_map(|row| row.first())
+        .fold(0, |previous, &first| first - previous)
+}
+
+/// A polynomial with exact coefficients, constant term first.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Polynomial {
+    pub coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn evaluate(&self, x: &Rational) -> Rational {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |total, coefficient| total * x.clone() + coefficient.clone())
+    }
+}
+
+/// Finds the lowest-degree polynomial passing through every sample, trying each degree in turn
+/// against the leading samples and checking it on the rest. `None` if two samples share an `x`.
+pub fn fit_polynomial(samples: &[(i64, i64)]) -> Option<Polynomial> {
+    let samples: Vec<(Rational, Rational)> = samples
+        .iter()
+        .map(|&(x, y)| (Rational::from(x), Rational::from(y)))
+        .collect();
+
+    (1..=samples.len()).find_map(|terms| {
+        let (leading, rest) = samples.split_at(terms);
+
+        let vandermonde: Vec<Vec<Rational>> = leading
+            .iter()
+            .map(|(x, _)| {
+                (0..terms)
+                    .scan(Rational::one(), |power, _| {
+                        let current = power.clone();
+                        *power = power.clone() * x.clone();
+                        Some(current)
+                    })
+                    .collect()
+            })
+            .collect();
+        let values: Vec<Rational> = leading.iter().map(|(_, y)| y.clone()).collect();
+
+        let mut coefficients = solve_linear_system(&vandermonde, &values)?;
+        while coefficients.len() > 1 && coefficients.last().is_some_and(|last| last.is_zero()) {
+            coefficients.pop();
+        }
+        let polynomial = Polynomial { coefficients };
+
+        rest.iter()
+            .all(|(x, y)| polynomial.evaluate(x) == *y)
+            .then_some(polynomial)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(checked_lcm(4, 6), Some(12));
+        assert_eq!(checked_lcm(-4, 6), Some(12));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+
+        // the product overflows but the least common multiple doesn't
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(checked_lcm(1u64 << 63, 3), None);
+        assert_eq!(checked_lcm(i64::MIN, 3), None);
+    }
+
+    #[test]
+    fn extended_gcd_finds_bezout_coefficients() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (17, 5), (0, 9), (9, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g, "{} * {} + {} * {}", a, x, b, y);
+        }
+    }
+
+    #[test]
+    fn inverse_and_power() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(7, 5, 1), 0);
+        assert_eq!(mod_pow(3u32, 200, 1_000_000_007), mod_pow(3i64, 200, 1_000_000_007) as u32);
+
+        // products of values near the modulus need the overflow-free multiplication
+        let modulus = i64::MAX - 24; // a prime
+        assert_eq!(mod_pow(2, modulus - 1, modulus), 1);
+        assert_eq!(mul_mod(modulus - 1, modulus - 1, modulus), 1);
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 4), (0, 3)]), Some((3, 12)));
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (1, 0)]), None);
+        assert_eq!(crt(&[(2, -3)]), None);
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        assert_eq!(crt(&[(2, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn crt_near_the_limits() {
+        let large = (1i64 << 61) - 1; // a prime
+        assert_eq!(crt(&[(large - 2, large), (0, 2)]), Some((2 * large - 2, 2 * large)));
+        assert_eq!(crt(&[(1, large), (1, large - 2), (0, 5)]), None);
+    }
+
+    #[test]
+    fn sieve() {
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(1), []);
+        assert_eq!(primes_up_to(100_000).len(), 9592);
+
+        // spans several segments and starts partway through one
+        let between = primes_between(1_000_000, 1_100_000);
+        assert_eq!(between.len(), 7216);
+        assert_eq!(between.first(), Some(&1_000_003));
+        assert!(between.iter().all(|&prime| is_prime(prime)));
+
+        let high = 1 << 40;
+        assert_eq!(primes_between(high - 100, high), [high - 87]);
+    }
+
+    #[test]
+    fn integer_square_roots() {
+        assert_eq!(integer_sqrt(0), 0);
+        assert_eq!(integer_sqrt(15), 3);
+        assert_eq!(integer_sqrt(16), 4);
+        assert_eq!(integer_sqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(integer_sqrt((1 << 52) * (1 << 10) - 1), (1 << 31) - 1);
+    }
+
+    #[test]
+    fn primality() {
+        let small: Vec<u64> = (0..30).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+
+        assert!(is_prime(u64::MAX - 58));
+        assert!(!is_prime(u64::MAX));
+        assert!(!is_prime(u64::MAX - 2));
+        // a strong pseudoprime to every prime base up to 23, only the later witnesses catch it
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        // Carmichael number
+        assert!(!is_prime(561));
+        assert!(is_prime((1 << 61) - 1));
+    }
+
+    #[test]
+    fn factorization() {
+        assert_eq!(trial_division(360), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(360), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1), []);
+        assert_eq!(factorize(0), []);
+
+        // two large primes that trial division alone couldn't split
+        let product = 4_294_967_291 * 4_294_967_279;
+        assert_eq!(factorize(product), [(4_294_967_279, 1), (4_294_967_291, 1)]);
+        let fermat = [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65537, 1), (6_700_417, 1)];
+        assert_eq!(factorize(u64::MAX), fermat);
+
+        // the rho step has to add without overflowing this close to the top of the range
+        let near_max = u64::MAX - 38;
+        assert_eq!(factorize(near_max), [(139_646_831, 1), (132_095_686_967, 1)]);
+    }
+
+    #[test]
+    fn divisors_and_totient() {
+        assert_eq!(divisors(12), [1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(1), [1]);
+        assert_eq!(divisors(0), []);
+        assert_eq!(divisors(720_720).len(), 240);
+
+        assert_eq!(totient(1), 1);
+        assert_eq!(totient(36), 12);
+        assert_eq!(totient(97), 96);
+    }
+
+    #[test]
+    fn lagrange_over_a_quadratic() {
+        // y = x² - 3x + 2
+        let samples: Vec<(Rational, Rational)> = [(0, 2), (1, 0), (3, 2)]
+            .into_iter()
+            .map(|(x, y)| (Rational::from(x), Rational::from(y)))
+            .collect();
+        assert_eq!(lagrange_interpolate(&samples, &Rational::from(10)), Rational::from(72));
+        assert_eq!(lagrange_interpolate(&samples, &Rational::new(3, 2)), Rational::new(-1, 4));
+    }
+
+    #[test]
+    fn extrapolation() {
+        assert_eq!(extrapolate_forward(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate_backward(&[0, 3, 6, 9, 12, 15]), -3);
+        assert_eq!(extrapolate_forward(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(extrapolate_backward(&[10, 13, 16, 21, 30, 45]), 5);
+        assert_eq!(extrapolate_forward(&[7]), 7);
+        assert_eq!(extrapolate_forward(&[]), 0);
+    }
+
+    #[test]
+    fn fitting_polynomials() {
+        // 2x³ - x + 5
+        let samples: Vec<(i64, i64)> = (-2..4).map(|x| (x, 2 * x * x * x - x + 5)).collect();
+        let polynomial = fit_polynomial(&samples).unwrap();
+        assert_eq!(polynomial.degree(), 3);
+        let expected: Vec<Rational> = [5, -1, 0, 2].into_iter().map(Rational::from).collect();
+        assert_eq!(polynomial.coefficients, expected);
+        assert_eq!(polynomial.evaluate(&Rational::from(10)), Rational::from(1995));
+
+        let constant = fit_polynomial(&[(1, 4), (5, 4), (9, 4)]).unwrap();
+        assert_eq!(constant.degree(), 0);
+    }
+
+    #[test]
+    fn fitting_rejects_conflicting_samples() {
+        assert_eq!(fit_polynomial(&[(1, 2), (1, 3)]), None);
+        assert_eq!(fit_polynomial(&[(0, 0), (2, 1), (2, 5)]), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use num::PrimInt;
+use crate::year2022::math;
+
+/// A small dense integer matrix stored row by row.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Matrix<T> {
+    pub rows: usize,
+    pub cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: PrimInt> Matrix<T> {
+    pub fn zero(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            data: vec![T::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut matrix = Matrix::zero(size, size);
+        for i in 0..size {
+            matrix[(i, i)] = T::one();
+        }
+        matrix
+    }
+
+    #[cfg(test)]
+    pub fn from_rows(rows: &[Vec<T>]) -> Matrix<T> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == cols), "every row must be the same length");
+
+        Matrix {
+            rows: rows.len(),
+            cols,
+            data: rows.iter().flatten().cloned().collect(),
+        }
+    }
+
+    /// A single column matrix holding the values.
+    pub fn column(values: &[T]) -> Matrix<T> {
+        Matrix {
+            rows: values.len(),
+            cols: 1,
+            data: values.to_vec(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    fn multiply<F>(&self, other: &Matrix<T>, mut multiply_add: F) -> Option<Matrix<T>>
+    where
+        F: FnMut(T, T, T) -> Option<T>,
+    {
+        assert_eq!(self.cols, other.rows, "matrix dimensions don't line up");
+
+        let mut result = Matrix::zero(self.rows, other.cols);
+        for row in 0..self.rows {
+            for col in 0..other.cols {
+                let mut sum = T::zero();
+                for i in 0..self.cols {
+                    sum = multiply_add(sum, self[(row, i)], other[(i, col)])?;
+                }
+                result[(row, col)] = sum;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// The product, or `None` if any entry overflows `T`.
+    pub fn checked_mul(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        self.multiply(other, |sum, a, b| sum.checked_add(&a.checked_mul(&b)?))
+    }
+
+    /// The product with every entry reduced modulo `modulus`.
+    pub fn mul_mod(&self, other: &Matrix<T>, modulus: T) -> Matrix<T> {
+        self.multiply(other, |sum, a, b| {
+            let product = math::mul_mod(math::modulo(a, modulus), math::modulo(b, modulus), modulus);
+            Some(math::add_mod(sum, product, modulus))
+        })
+        .unwrap()
+    }
+
+    fn power<F>(&self, exponent: u64, mut multiply: F) -> Option<Matrix<T>>
+    where
+        F: FnMut(&Matrix<T>, &Matrix<T>) -> Option<Matrix<T>>,
+    {
+        assert_eq!(self.rows, self.cols, "only square matrices can be raised to a power");
+
+        let mut result = Matrix::identity(self.rows);
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = multiply(&result, &base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = multiply(&base, &base)?;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Raises a square matrix to `exponent` by repeated squaring, or `None` if any entry
+    /// overflows `T` along the way.
+    pub fn checked_pow(&self, exponent: u64) -> Option<Matrix<T>> {
+        self.power(exponent, Matrix::checked_mul)
+    }
+
+    /// Raises a square matrix to `exponent` by repeated squaring, reducing modulo `modulus`.
+    pub fn pow_mod(&self, exponent: u64, modulus: T) -> Matrix<T> {
+        self.power(exponent, |a, b| Some(a.mul_mod(b, modulus))).unwrap()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl<T: Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.data.chunks(self.cols.max(1)) {
+            let row: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci() -> Matrix<u64> {
+        Matrix::from_rows(&[vec![1, 1], vec![1, 0]])
+    }
+
+    #[test]
+    fn multiplication() {
+        let a = Matrix::from_rows(&[vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::column(&[1, 0, -1]);
+        assert_eq!(a.checked_mul(&b), Some(Matrix::column(&[-2, -2])));
+        assert_eq!(a.checked_mul(&Matrix::identity(3)), Some(a.clone()));
+        assert_eq!(a.to_string(), "1 2 3\n4 5 6\n");
+    }
+
+    #[test]
+    fn powers() {
+        assert_eq!(fibonacci().checked_pow(0), Some(Matrix::identity(2)));
+        assert_eq!(fibonacci().checked_pow(10).unwrap()[(0, 1)], 55);
+        // the top left entry of the 92nd power is the largest Fibonacci number a u64 holds
+        assert_eq!(fibonacci().checked_pow(92).unwrap()[(0, 0)], 12_200_160_415_121_876_738);
+        assert_eq!(fibonacci().checked_pow(93), None);
+    }
+
+    #[test]
+    fn powers_modulo() {
+        assert_eq!(fibonacci().pow_mod(10, 7)[(0, 1)], 55 % 7);
+        // the Pisano period for 10 is 60
+        let modulo_ten = fibonacci().pow_mod(1_000_000_000_000, 10);
+        assert_eq!(modulo_ten, fibonacci().pow_mod(1_000_000_000_000 % 60, 10));
+
+        // entries close to the modulus mustn't overflow while multiplying
+        let modulus = u64::MAX - 58;
+        let large = Matrix::from_rows(&[vec![modulus - 1]]);
+        assert_eq!(large.pow_mod(2, modulus)[(0, 0)], 1);
+        assert_eq!(large.pow_mod(3, modulus)[(0, 0)], modulus - 1);
+    }
+}
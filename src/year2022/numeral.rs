@@ -0,0 +1,285 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use num::PrimInt;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseNumeralError {
+    Empty,
+    InvalidDigit(char),
+    Overflow,
+}
+
+impl Display for ParseNumeralError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseNumeralError::Empty => write!(f, "numeral has no digits"),
+            ParseNumeralError::InvalidDigit(c) => write!(f, "'{}' is not a digit of this base", c),
+            ParseNumeralError::Overflow => write!(f, "numeral is too large for the target type"),
+        }
+    }
+}
+
+impl Error for ParseNumeralError {}
+
+fn checked_push_digit<T: PrimInt>(value: T, base: T, digit: T) -> Result<T, ParseNumeralError> {
+    value
+        .checked_mul(&base)
+        .and_then(|value| value.checked_add(&digit))
+        .ok_or(ParseNumeralError::Overflow)
+}
+
+/// Writes `value` in any base from 2 to 36 with lowercase letters past 9 and a leading `-` for
+/// negative numbers.
+pub fn to_base<T: PrimInt>(value: T, base: u32) -> String {
+    assert!((2..=36).contains(&base), "base must be between 2 and 36");
+
+    let negative = value < T::zero();
+    let base = T::from(base).unwrap();
+
+    let mut digits = Vec::new();
+    let mut remaining = value;
+    loop {
+        // work with negative remainders so the minimum value of a signed type doesn't overflow
+        let digit = (remaining % base).to_i64().unwrap().unsigned_abs();
+        digits.push(DIGITS[digit as usize] as char);
+        remaining = remaining / base;
+        if remaining.is_zero() {
+            break;
+        }
+    }
+
+    if negative {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+/// Reads a numeral in any base from 2 to 36, accepting either letter case and a leading `-`.
+pub fn from_base<T: PrimInt>(s: &str, base: u32) -> Result<T, ParseNumeralError> {
+    assert!((2..=36).contains(&base), "base must be between 2 and 36");
+
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    if digits.is_empty() {
+        return Err(ParseNumeralError::Empty);
+    }
+
+    let radix = T::from(base).unwrap();
+    digits.chars().try_fold(T::zero(), |value, c| {
+        let digit = c.to_digit(base).ok_or(ParseNumeralError::InvalidDigit(c))?;
+        let digit = T::from(digit).ok_or(ParseNumeralError::Overflow)?;
+
+        if negative {
+            value
+                .checked_mul(&radix)
+                .and_then(|value| value.checked_sub(&digit))
+                .ok_or(ParseNumeralError::Overflow)
+        } else {
+            checked_push_digit(value, radix, digit)
+        }
+    })
+}
+
+/// A base with digits running from `-(base / 2)` to `base / 2`, like balanced ternary or the
+/// SNAFU numbers of 2022 day 25. The alphabet lists the digits in ascending value so its middle
+/// character stands for zero.
+#[derive(Debug, Clone)]
+pub struct BalancedBase {
+    alphabet: Vec<char>,
+}
+
+impl BalancedBase {
+    pub fn new(alphabet: &str) -> BalancedBase {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        assert!(
+            alphabet.len() >= 3 && alphabet.len() % 2 == 1,
+            "a balanced base needs an odd number of digits"
+        );
+
+        BalancedBase { alphabet }
+    }
+
+    /// Balanced base five with `=` and `-` for minus two and minus one.
+    pub fn snafu() -> BalancedBase {
+        BalancedBase::new("=-012")
+    }
+
+    fn base(&self) -> i64 {
+        self.alphabet.len() as i64
+    }
+
+    fn offset(&self) -> i64 {
+        self.base() / 2
+    }
+
+    pub fn format(&self, value: i64) -> String {
+        let base = self.base() as i128;
+        let offset = self.offset() as i128;
+
+        let mut digits = Vec::new();
+        let mut remaining = value as i128;
+        loop {
+            let digit = (remaining + offset).rem_euclid(base) - offset;
+            digits.push(self.alphabet[(digit + offset) as usize]);
+            remaining = (remaining - digit) / base;
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        digits.iter().rev().collect()
+    }
+
+    pub fn parse(&self, s: &str) -> Result<i64, ParseNumeralError> {
+        if s.is_empty() {
+            return Err(ParseNumeralError::Empty);
+        }
+
+        // a prefix can fall outside i64 even when the whole numeral doesn't, so widen until the end
+        let value = s.chars().try_fold(0i128, |value, c| {
+            let index = self
+                .alphabet
+                .iter()
+                .position(|&digit| digit == c)
+                .ok_or(ParseNumeralError::InvalidDigit(c))?;
+
+            checked_push_digit(value, self.base() as i128, (index as i64 - self.offset()) as i128)
+        })?;
+
+        i64::try_from(value).map_err(|_| ParseNumeralError::Overflow)
+    }
+}
+
+/// Reflected binary code, where consecutive values differ in a single bit.
+pub fn to_gray<T: PrimInt>(value: T) -> T {
+    value ^ value.unsigned_shr(1)
+}
+
+pub fn from_gray<T: PrimInt>(gray: T) -> T {
+    let mut value = gray;
+    let mut shift = 1;
+    while shift < T::zero().count_zeros() {
+        value = value ^ value.unsigned_shr(shift);
+        shift *= 2;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAFU_TABLE: [(i64, &str); 22] = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (6, "11"),
+        (7, "12"),
+        (8, "2="),
+        (9, "2-"),
+        (10, "20"),
+        (15, "1=0"),
+        (20, "1-0"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+        (1747, "1=-0-2"),
+        (906, "12111"),
+        (198, "2=0="),
+        (201, "2=01"),
+        (31, "111"),
+        (1257, "20012"),
+        (4890, "2=-1=0"),
+    ];
+
+    #[test]
+    fn snafu_format() {
+        let snafu = BalancedBase::snafu();
+        for (value, numeral) in SNAFU_TABLE {
+            assert_eq!(snafu.format(value), numeral, "formatting {}", value);
+        }
+    }
+
+    #[test]
+    fn snafu_parse() {
+        let snafu = BalancedBase::snafu();
+        for (value, numeral) in SNAFU_TABLE {
+            assert_eq!(snafu.parse(numeral), Ok(value), "parsing {}", numeral);
+        }
+    }
+
+    #[test]
+    fn snafu_round_trips_extremes() {
+        let snafu = BalancedBase::snafu();
+        for value in [0, -1, -2022, i64::MAX, i64::MIN] {
+            assert_eq!(snafu.parse(&snafu.format(value)), Ok(value));
+        }
+    }
+
+    #[test]
+    fn snafu_rejects_bad_numerals() {
+        let snafu = BalancedBase::snafu();
+        assert_eq!(snafu.parse(""), Err(ParseNumeralError::Empty));
+        assert_eq!(snafu.parse("1=3"), Err(ParseNumeralError::InvalidDigit('3')));
+        assert_eq!(snafu.parse(&"2".repeat(40)), Err(ParseNumeralError::Overflow));
+    }
+
+    #[test]
+    fn balanced_ternary() {
+        let ternary = BalancedBase::new("T01");
+        assert_eq!(ternary.format(8), "10T");
+        assert_eq!(ternary.format(-8), "T01");
+        assert_eq!(ternary.parse("10T"), Ok(8));
+    }
+
+    #[test]
+    fn to_base_examples() {
+        assert_eq!(to_base(255u8, 16), "ff");
+        assert_eq!(to_base(0i32, 2), "0");
+        assert_eq!(to_base(-10i32, 2), "-1010");
+        assert_eq!(to_base(35u32, 36), "z");
+        assert_eq!(to_base(i64::MIN, 16), "-8000000000000000");
+    }
+
+    #[test]
+    fn from_base_examples() {
+        assert_eq!(from_base::<u8>("FF", 16), Ok(255));
+        assert_eq!(from_base::<i32>("-1010", 2), Ok(-10));
+        assert_eq!(from_base::<u8>("100", 16), Err(ParseNumeralError::Overflow));
+        assert_eq!(from_base::<u32>("12", 2), Err(ParseNumeralError::InvalidDigit('2')));
+        assert_eq!(from_base::<i32>("-", 10), Err(ParseNumeralError::Empty));
+    }
+
+    #[test]
+    fn base_round_trips() {
+        for base in 2..=36 {
+            for value in [0, 1, -1, 1234567, -1234567, i64::MAX, i64::MIN] {
+                assert_eq!(from_base::<i64>(&to_base(value, base), base), Ok(value));
+            }
+            assert_eq!(from_base::<u64>(&to_base(u64::MAX, base), base), Ok(u64::MAX));
+        }
+    }
+
+    #[test]
+    fn gray_code() {
+        assert_eq!(to_gray(0u8), 0);
+        assert_eq!(to_gray(2u8), 0b11);
+        assert_eq!(to_gray(7u8), 0b100);
+
+        for value in 0..=u8::MAX {
+            assert_eq!(from_gray(to_gray(value)), value);
+            let next = value.wrapping_add(1);
+            assert_eq!((to_gray(value) ^ to_gray(next)).count_ones(), 1);
+        }
+        assert_eq!(from_gray(to_gray(u64::MAX)), u64::MAX);
+        assert_eq!(from_gray(to_gray(i64::MIN)), i64::MIN);
+    }
+}
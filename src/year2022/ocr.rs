@@ -0,0 +1,203 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::year2022::grid::Grid;
+
+/// The 4×6 block letters used by most puzzles, each in a five column cell.
+const SMALL_FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// The 6×10 block letters used by a few puzzles, each in an eight column cell.
+const LARGE_FONT: [(char, &str); 15] = [
+    ('A', "..##...#..#.#....##....##....########....##....##....##....#"),
+    ('B', "#####.#....##....##....######.#....##....##....##....######."),
+    ('C', ".####.#....##.....#.....#.....#.....#.....#.....#....#.####."),
+    ('E', "#######.....#.....#.....#####.#.....#.....#.....#.....######"),
+    ('F', "#######.....#.....#.....#####.#.....#.....#.....#.....#....."),
+    ('G', ".####.#....##.....#.....#.....#..####....##....##...##.###.#"),
+    ('H', "#....##....##....##....########....##....##....##....##....#"),
+    ('J', "...###....#.....#.....#.....#.....#.....#.#...#.#...#..###.."),
+    ('K', "#....##...#.#..#..#.#...##....##....#.#...#..#..#...#.#....#"),
+    ('L', "#.....#.....#.....#.....#.....#.....#.....#.....#.....######"),
+    ('N', "#....###...###...##.#..##.#..##..#.##..#.##...###...###....#"),
+    ('P', "#####.#....##....##....######.#.....#.....#.....#.....#....."),
+    ('R', "#####.#....##....##....######.#..#..#...#.#...#.#....##....#"),
+    ('X', "#....##....#.#..#..#..#...##....##...#..#..#..#.#....##....#"),
+    ('Z', "######.....#.....#....#....#....#....#....#.....#.....######"),
+];
+
+struct Font {
+    glyph_width: usize,
+    cell_width: usize,
+    height: usize,
+    glyphs: &'static [(char, &'static str)],
+}
+
+const SMALL: Font = Font {
+    glyph_width: 4,
+    cell_width: 5,
+    height: 6,
+    glyphs: &SMALL_FONT,
+};
+
+const LARGE: Font = Font {
+    glyph_width: 6,
+    cell_width: 8,
+    height: 10,
+    glyphs: &LARGE_FONT,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OcrError {
+    /// No font is this many pixels tall.
+    Height(usize),
+    /// Zero-based positions of the glyphs that didn't match any letter.
+    Unrecognized(Vec<usize>),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "no font is {} pixels tall, expected 6 or 10", height)
+            }
+            OcrError::Unrecognized(positions) => {
+                let positions: Vec<String> = positions.iter().map(|p| p.to_string()).collect();
+                write!(f, "unrecognized glyphs at positions {}", positions.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Reads the letters drawn in a 6 or 10 pixel tall image.
+pub fn recognize(pixels: &Grid<bool>) -> Result<String, OcrError> {
+    let font = match pixels.height {
+        6 => SMALL,
+        10 => LARGE,
+        height => return Err(OcrError::Height(height)),
+    };
+
+    let lit = |x: usize, y: usize| x < pixels.width && pixels.data[pixels.index(x, y)];
+
+    let mut letters = String::new();
+    let mut unrecognized = Vec::new();
+    for (position, left) in (0..pixels.width).step_by(font.cell_width).enumerate() {
+        // letters never touch the spacing columns, anything lit there can't be a glyph
+        let spacing_lit = (font.glyph_width..font.cell_width)
+            .any(|x| (0..font.height).any(|y| lit(left + x, y)));
+
+        let pattern: String = (0..font.height)
+            .flat_map(|y| (0..font.glyph_width).map(move |x| (x, y)))
+            .map(|(x, y)| if lit(left + x, y) { '#' } else { '.' })
+            .collect();
+
+        if !spacing_lit && !pattern.contains('#') {
+            continue;
+        }
+
+        match font.glyphs.iter().find(|&&(_, glyph)| glyph == pattern) {
+            Some(&(letter, _)) if !spacing_lit => letters.push(letter),
+            _ => unrecognized.push(position),
+        }
+    }
+
+    if unrecognized.is_empty() {
+        Ok(letters)
+    } else {
+        Err(OcrError::Unrecognized(unrecognized))
+    }
+}
+
+/// Reads the letters from rows of pixels laid out one after another, where any non-zero value
+/// is lit.
+pub fn recognize_buffer(pixels: &[i32], width: usize) -> Result<String, OcrError> {
+    let mut grid = Grid::new(width, pixels.len() / width, || false);
+    for (cell, &pixel) in grid.iter_mut().zip(pixels) {
+        *cell = pixel != 0;
+    }
+
+    recognize(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` in `font`, with glyphs from the font table and blank spacing columns.
+    fn draw(font: &Font, text: &str) -> Grid<bool> {
+        let mut pixels = Grid::new(text.len() * font.cell_width, font.height, || false);
+        for (position, letter) in text.chars().enumerate() {
+            let &(_, glyph) = font.glyphs.iter().find(|&&(c, _)| c == letter).unwrap();
+            for (i, pixel) in glyph.chars().enumerate() {
+                let x = position * font.cell_width + i % font.glyph_width;
+                let index = pixels.index(x, i / font.glyph_width);
+                pixels.data[index] = pixel == '#';
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn small_letters() {
+        assert_eq!(recognize(&draw(&SMALL, "EHZ")), Ok("EHZ".to_string()));
+        assert_eq!(recognize(&draw(&SMALL, "PLUG")), Ok("PLUG".to_string()));
+    }
+
+    #[test]
+    fn large_letters() {
+        assert_eq!(recognize(&draw(&LARGE, "NXJ")), Ok("NXJ".to_string()));
+    }
+
+    #[test]
+    fn blank_cells_are_skipped() {
+        let pixels = draw(&SMALL, "A");
+        let mut wide = Grid::new(pixels.width + SMALL.cell_width, SMALL.height, || false);
+        for y in 0..SMALL.height {
+            for x in 0..pixels.width {
+                let index = wide.index(x + SMALL.cell_width, y);
+                wide.data[index] = pixels.data[pixels.index(x, y)];
+            }
+        }
+        assert_eq!(recognize(&wide), Ok("A".to_string()));
+    }
+
+    #[test]
+    fn errors() {
+        let mut pixels = draw(&SMALL, "ABC");
+        let index = pixels.index(6, 0);
+        pixels.data[index] = !pixels.data[index];
+        let index = pixels.index(14, 5);
+        pixels.data[index] = true;
+        assert_eq!(recognize(&pixels), Err(OcrError::Unrecognized(vec![1, 2])));
+
+        let short = Grid::new(5, 4, || false);
+        assert_eq!(recognize(&short), Err(OcrError::Height(4)));
+    }
+
+    #[test]
+    fn flat_buffers() {
+        let pixels = draw(&SMALL, "CF");
+        let buffer: Vec<i32> = pixels.data.iter().map(|&lit| if lit { 1 } else { 0 }).collect();
+        assert_eq!(recognize_buffer(&buffer, pixels.width), Ok("CF".to_string()));
+    }
+}
@@ -1,9 +1,11 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
 use std::ops::{Add, Div, Mul, Sub, AddAssign, SubAssign, MulAssign, DivAssign, Neg};
 use std::str::FromStr;
+use num::PrimInt;
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
 pub struct Point<T> {
     pub x: T,
     pub y: T,
@@ -165,3 +167,172 @@ impl<T: FromStr> FromStr for Point<T> {
         })
     }
 }
+
+impl<T: PrimInt> Point<T> {
+    /// Every lattice point from `self` to `end` inclusive, using Bresenham's algorithm so
+    /// axis-aligned and 45° segments come out exact. Fails if a coordinate doesn't fit in 64 bits,
+    /// so the stepping can't overflow the `i128` it's done in.
+    pub fn line_to(self, end: Point<T>) -> Result<Line<T>, LineRangeError> {
+        Line::new(self, end)
+    }
+
+    /// Steps between the two points when moving only horizontally and vertically.
+    pub fn manhattan_distance(self, other: Point<T>) -> T {
+        let difference = |a: T, b: T| if a > b { a - b } else { b - a };
+        difference(self.x, other.x) + difference(self.y, other.y)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Line<T> {
+    current: Point<i128>,
+    end: Point<i128>,
+    step: Point<i128>,
+    delta: Point<i128>,
+    error: i128,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: PrimInt> Line<T> {
+    fn new(start: Point<T>, end: Point<T>) -> Result<Line<T>, LineRangeError> {
+        let current = to_wide(start)?;
+        let end = to_wide(end)?;
+        let delta = Point::new((end.x - current.x).abs(), -(end.y - current.y).abs());
+
+        Ok(Line {
+            current,
+            end,
+            step: Point::new((end.x - current.x).signum(), (end.y - current.y).signum()),
+            delta,
+            error: delta.x + delta.y,
+            done: false,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: PrimInt> Iterator for Line<T> {
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let point = self.current;
+        if point == self.end {
+            self.done = true;
+        } else {
+            let doubled_error = self.error * 2;
+            if doubled_error >= self.delta.y {
+                self.error += self.delta.y;
+                self.current.x += self.step.x;
+            }
+            if doubled_error <= self.delta.x {
+                self.error += self.delta.x;
+                self.current.y += self.step.y;
+            }
+        }
+
+        // every yielded coordinate lies between the two endpoints so it always fits in T
+        Some(Point::new(T::from(point.x).unwrap(), T::from(point.y).unwrap()))
+    }
+}
+
+/// Every lattice point along a path of connected segments, yielding shared corners once. A path
+/// of a single point yields just that point. Fails up front if any corner is out of range for
+/// `line_to`.
+pub fn polyline<T: PrimInt>(
+    points: &[Point<T>],
+) -> Result<impl Iterator<Item = Point<T>> + '_, LineRangeError> {
+    for &point in points {
+        to_wide(point)?;
+    }
+    let lone = points.first().filter(|_| points.len() == 1).copied();
+
+    // every corner was checked above, so no segment is dropped by flattening its `Result`
+    Ok(points
+        .iter()
+        .zip(points.iter().skip(1))
+        .enumerate()
+        .flat_map(|(i, (&from, &to))| {
+            from.line_to(to).into_iter().flatten().skip((i > 0) as usize)
+        })
+        .chain(lone))
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct LineRangeError;
+
+impl Display for LineRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line coordinates must fit in 64 bits")
+    }
+}
+
+impl Error for LineRangeError {}
+
+fn to_wide<T: PrimInt>(point: Point<T>) -> Result<Point<i128>, LineRangeError> {
+    let wide = |value: T| {
+        let value = value.to_i64().map(i128::from).or_else(|| value.to_u64().map(i128::from));
+        value.ok_or(LineRangeError)
+    };
+    Ok(Point::new(wide(point.x)?, wide(point.y)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_to() {
+        let line: Vec<Point<i32>> = Point::new(0, 0).line_to(Point::new(3, 0)).unwrap().collect();
+        assert_eq!(line, [Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]);
+
+        let diagonal: Vec<Point<u8>> = Point::new(2, 2).line_to(Point::new(0, 0)).unwrap().collect();
+        assert_eq!(diagonal, [Point::new(2, 2), Point::new(1, 1), Point::new(0, 0)]);
+
+        let steep: Vec<Point<i64>> = Point::new(0, 0).line_to(Point::new(1, 3)).unwrap().collect();
+        assert_eq!(steep.len(), 4);
+        assert_eq!(steep.last(), Some(&Point::new(1, 3)));
+    }
+
+    #[test]
+    fn polyline_shares_corners() {
+        let path = [Point::new(498, 4), Point::new(498, 6), Point::new(496, 6)];
+        let points: Vec<Point<i32>> = polyline(&path).unwrap().collect();
+        assert_eq!(
+            points,
+            [
+                Point::new(498, 4),
+                Point::new(498, 5),
+                Point::new(498, 6),
+                Point::new(497, 6),
+                Point::new(496, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn polyline_of_one_point() {
+        let points: Vec<Point<i32>> = polyline(&[Point::new(5, 7)]).unwrap().collect();
+        assert_eq!(points, [Point::new(5, 7)]);
+
+        assert_eq!(polyline::<i32>(&[]).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let far = Point::new(u128::MAX, 0);
+        assert!(Point::new(0u128, 0).line_to(far).is_err());
+        assert!(polyline(&[Point::new(0u128, 0), Point::new(1, 0), far]).is_err());
+
+        let edge = Point::new(0u64, u64::MAX).line_to(Point::new(u64::MAX, 0)).unwrap();
+        let corners = [Point::new(0, u64::MAX), Point::new(1, u64::MAX - 1)];
+        assert_eq!(edge.take(2).collect::<Vec<_>>(), corners);
+
+        let mut low = Point::new(i64::MIN, 0).line_to(Point::new(i64::MAX, 0)).unwrap();
+        assert_eq!(low.nth(1), Some(Point::new(i64::MIN + 1, 0)));
+    }
+}
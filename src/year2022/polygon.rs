@@ -0,0 +1,169 @@
+#![allow(dead_code)]
+
+use crate::year2022::math;
+use crate::year2022::point::Point;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A simple polygon on the integer lattice, with an implicit edge from the last vertex back
+/// to the first.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Polygon {
+    pub vertices: Vec<Point<i64>>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point<i64>>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        self.vertices
+            .iter()
+            .cloned()
+            .zip(self.vertices.iter().cloned().cycle().skip(1))
+    }
+
+    /// Twice the signed area from the shoelace formula, positive when the vertices run
+    /// counter-clockwise with y pointing up. Doubling keeps half-unit areas exact.
+    pub fn doubled_signed_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum()
+    }
+
+    pub fn doubled_area(&self) -> i64 {
+        self.doubled_signed_area().abs()
+    }
+
+    /// Number of lattice points lying on the edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| {
+                let Point { x, y } = b - a;
+                math::gcd(x, y)
+            })
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, from Pick's theorem `A = i + b/2 - 1`.
+    pub fn interior_points(&self) -> i64 {
+        (self.doubled_area() - self.boundary_points() + 2) / 2
+    }
+
+    /// Number of lattice points inside or on the boundary, e.g. cells dug by a trench loop.
+    pub fn enclosed_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn contains(&self, point: Point<i64>) -> Containment {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+            let within_x = a.x.min(b.x) <= point.x && point.x <= a.x.max(b.x);
+            let within_y = a.y.min(b.y) <= point.y && point.y <= a.y.max(b.y);
+            if cross == 0 && within_x && within_y {
+                return Containment::Boundary;
+            }
+
+            // cast a ray towards +x and count the edges it crosses, treating each edge as
+            // half-open in y so shared vertices are only counted once
+            if (a.y > point.y) != (b.y > point.y) {
+                let crosses_right = if b.y > a.y { cross > 0 } else { cross < 0 };
+                if crosses_right {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+}
+
+impl FromIterator<Point<i64>> for Polygon {
+    fn from_iter<I: IntoIterator<Item = Point<i64>>>(iter: I) -> Self {
+        Polygon::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Polygon {
+        vertices.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn picks_theorem_on_a_square() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.doubled_signed_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.enclosed_points(), 25);
+    }
+
+    #[test]
+    fn orientation_sets_the_sign() {
+        let clockwise = polygon(&[(0, 0), (0, 4), (4, 4), (4, 0)]);
+        assert_eq!(clockwise.doubled_signed_area(), -32);
+        assert_eq!(clockwise.doubled_area(), 32);
+        assert_eq!(clockwise.interior_points(), 9);
+    }
+
+    #[test]
+    fn diagonal_edges() {
+        // the only lattice points on the slanted edge are its ends
+        let triangle = polygon(&[(0, 0), (3, 0), (0, 2)]);
+        assert_eq!(triangle.doubled_area(), 6);
+        assert_eq!(triangle.boundary_points(), 6);
+        assert_eq!(triangle.interior_points(), 1);
+    }
+
+    #[test]
+    fn trench_loop() {
+        // a rectilinear loop traced through its corners, with notches on both sides
+        let lagoon = polygon(&[
+            (0, 0),
+            (6, 0),
+            (6, 5),
+            (4, 5),
+            (4, 7),
+            (6, 7),
+            (6, 9),
+            (1, 9),
+            (1, 7),
+            (0, 7),
+            (0, 5),
+            (2, 5),
+            (2, 2),
+            (0, 2),
+        ]);
+        assert_eq!(lagoon.enclosed_points(), 62);
+    }
+
+    #[test]
+    fn containment() {
+        let shape = polygon(&[(0, 0), (6, 0), (6, 6), (3, 3), (0, 6)]);
+        assert_eq!(shape.contains(Point::new(1, 1)), Containment::Inside);
+        assert_eq!(shape.contains(Point::new(3, 0)), Containment::Boundary);
+        assert_eq!(shape.contains(Point::new(6, 6)), Containment::Boundary);
+        assert_eq!(shape.contains(Point::new(2, 2)), Containment::Inside);
+        assert_eq!(shape.contains(Point::new(3, 3)), Containment::Boundary);
+        assert_eq!(shape.contains(Point::new(3, 5)), Containment::Outside);
+        assert_eq!(shape.contains(Point::new(7, 3)), Containment::Outside);
+        // the ray from here passes straight through the vertex at (3, 3)
+        assert_eq!(shape.contains(Point::new(1, 3)), Containment::Inside);
+        assert_eq!(shape.contains(Point::new(-1, 3)), Containment::Outside);
+    }
+}
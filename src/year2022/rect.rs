@@ -0,0 +1,146 @@
+#![allow(dead_code)]
+
+use std::fmt::{Display, Formatter};
+use num::PrimInt;
+use crate::year2022::point::Point;
+
+/// An axis-aligned rectangle where both corners are inclusive.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: PrimInt> Rect<T> {
+    pub fn new(a: Point<T>, b: Point<T>) -> Rect<T> {
+        Rect {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle covering every point, or `None` when there are no points.
+    pub fn from_points<I>(points: I) -> Option<Rect<T>>
+    where
+        I: IntoIterator<Item = Point<T>>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Rect::new(first, first), |rect, point| rect.include(point)))
+    }
+
+    pub fn include(&self, point: Point<T>) -> Rect<T> {
+        self.union(&Rect::new(point, point))
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::one()
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::one()
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x &&
+            self.min.y <= point.y && point.y <= self.max.y
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x &&
+            self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        Rect {
+            min: Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        if self.intersects(other) {
+            Some(Rect {
+                min: Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+                max: Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Grows every side outwards by `margin`, saturating at the bounds of `T`.
+    pub fn expand(&self, margin: T) -> Rect<T> {
+        Rect {
+            min: Point::new(self.min.x.saturating_sub(margin), self.min.y.saturating_sub(margin)),
+            max: Point::new(self.max.x.saturating_add(margin), self.max.y.saturating_add(margin)),
+        }
+    }
+
+    /// Every point inside the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let Rect { min, max } = *self;
+        num::range_inclusive(min.y, max.y)
+            .flat_map(move |y| num::range_inclusive(min.x, max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+impl<T: Display> Display for Rect<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points() {
+        let points = [Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)];
+        let rect = Rect::from_points(points).unwrap();
+        assert_eq!(rect, Rect::new(Point::new(-2, -1), Point::new(3, 4)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (6, 6, 36));
+
+        assert_eq!(Rect::<i32>::from_points([]), None);
+    }
+
+    #[test]
+    fn containment_and_overlap() {
+        let a = Rect::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Rect::new(Point::new(4, 2), Point::new(6, 8));
+        let c = Rect::new(Point::new(5, 0), Point::new(6, 1));
+
+        assert!(a.contains(Point::new(4, 4)));
+        assert!(!a.contains(Point::new(5, 4)));
+
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&b), Some(Rect::new(Point::new(4, 2), Point::new(4, 4))));
+        assert!(!a.intersects(&c));
+        assert_eq!(a.intersection(&c), None);
+
+        assert_eq!(a.union(&c), Rect::new(Point::new(0, 0), Point::new(6, 4)));
+    }
+
+    #[test]
+    fn expand_saturates() {
+        let rect: Rect<usize> = Rect::new(Point::new(1, 5), Point::new(2, 6));
+        assert_eq!(rect.expand(3), Rect::new(Point::new(0, 2), Point::new(5, 9)));
+    }
+
+    #[test]
+    fn points_row_by_row() {
+        let rect = Rect::new(Point::new(1, 1), Point::new(2, 2));
+        let points: Vec<Point<u8>> = rect.points().collect();
+        assert_eq!(
+            points,
+            [Point::new(1, 1), Point::new(2, 1), Point::new(1, 2), Point::new(2, 2)]
+        );
+        assert_eq!(rect.to_string(), "1,1..2,2");
+    }
+}